@@ -0,0 +1,168 @@
+use crate::*;
+
+pub type AirdropIndex = u32;
+
+/// Leaves and internal nodes of the Merkle tree are hashed with different prefixes,
+/// so an internal node cannot be claimed as a leaf.
+pub const MERKLE_LEAF_PREFIX: u8 = 0;
+pub const MERKLE_NODE_PREFIX: u8 = 1;
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+pub struct AirdropCreate {
+    /// The root of the Merkle tree built from `(account_id, amount)` leaves.
+    pub merkle_root: Base58CryptoHash,
+    /// The schedule shared by all recipients. It is scaled to the amount of every leaf.
+    pub schedule: Schedule,
+    /// The total amount of all leaves, the airdrop has to be funded with this amount.
+    pub total_amount: WrappedBalance,
+    /// The leaves can be claimed until this timestamp. After it the payer can reclaim
+    /// the unclaimed balance.
+    pub claim_deadline: TimestampSec,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Airdrop {
+    pub merkle_root: CryptoHash,
+    pub schedule: Schedule,
+    pub total_amount: Balance,
+    pub claimed_amount: Balance,
+    pub payer_id: Option<ValidAccountId>,
+    pub claim_deadline: TimestampSec,
+    /// Whether the unclaimed balance was returned to the payer.
+    pub reclaimed: bool,
+}
+
+impl Airdrop {
+    pub fn new(airdrop_create: AirdropCreate) -> Self {
+        let total_balance = airdrop_create.schedule.total_balance();
+        airdrop_create.schedule.assert_valid(total_balance);
+        assert!(
+            airdrop_create.total_amount.0 > 0,
+            "expected total amount to be positive",
+        );
+        assert!(
+            airdrop_create.claim_deadline > current_timestamp_sec(),
+            "expected claim deadline in the future",
+        );
+        Self {
+            merkle_root: airdrop_create.merkle_root.into(),
+            schedule: airdrop_create.schedule,
+            total_amount: airdrop_create.total_amount.0,
+            claimed_amount: 0,
+            payer_id: None,
+            claim_deadline: airdrop_create.claim_deadline,
+            reclaimed: false,
+        }
+    }
+
+    /// Returns the balance held by the contract for the airdrop.
+    pub fn unclaimed_amount(&self) -> Balance {
+        if self.payer_id.is_none() || self.reclaimed {
+            0
+        } else {
+            self.total_amount - self.claimed_amount
+        }
+    }
+
+    /// Marks the unclaimed balance as returned to the payer and returns it.
+    pub fn reclaim(&mut self, account_id: &AccountId) -> Balance {
+        let payer_id = self
+            .payer_id
+            .as_ref()
+            .expect("cannot reclaim not funded airdrop");
+        assert_eq!(payer_id.as_ref(), account_id, "Not the airdrop payer");
+        assert!(
+            current_timestamp_sec() >= self.claim_deadline,
+            "The airdrop claim deadline has not passed",
+        );
+        assert!(!self.reclaimed, "airdrop already reclaimed");
+        let amount = self.unclaimed_amount();
+        self.reclaimed = true;
+        amount
+    }
+
+    pub fn assert_can_fund(&self) {
+        assert!(self.payer_id.is_none(), "airdrop already funded");
+    }
+
+    pub fn fund(&mut self, payer_id: &ValidAccountId) {
+        self.assert_can_fund();
+        self.payer_id = Some(payer_id.clone());
+    }
+
+    /// Verifies the Merkle proof of the leaf and returns the lockup for the recipient.
+    pub fn claim(
+        &mut self,
+        account_id: &ValidAccountId,
+        amount: Balance,
+        proof: &[Base58CryptoHash],
+    ) -> Lockup {
+        let payer_id = self
+            .payer_id
+            .clone()
+            .expect("cannot claim from not funded airdrop");
+        assert!(
+            current_timestamp_sec() < self.claim_deadline,
+            "The airdrop claim deadline has passed",
+        );
+        assert!(amount > 0, "expected amount to be positive");
+        let leaf = airdrop_leaf_hash(account_id.as_ref(), amount);
+        assert_eq!(
+            merkle_proof_root(leaf, proof),
+            self.merkle_root,
+            "Invalid Merkle proof"
+        );
+        let claimed_amount = self
+            .claimed_amount
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+        assert!(
+            claimed_amount <= self.total_amount,
+            "The airdrop total amount is exceeded"
+        );
+        self.claimed_amount = claimed_amount;
+
+        Lockup {
+            account_id: account_id.clone(),
+            schedule: self.schedule.scale(amount),
+            creator_id: payer_id,
+            claimed_balance: 0,
+            termination_config: None,
+            template_id: None,
+        }
+    }
+}
+
+pub(crate) fn airdrop_leaf_hash(account_id: &AccountId, amount: Balance) -> CryptoHash {
+    let value_hash = env::sha256(
+        &[
+            vec![MERKLE_LEAF_PREFIX],
+            (account_id, amount).try_to_vec().unwrap(),
+        ]
+        .concat(),
+    );
+    let mut res = CryptoHash::default();
+    res.copy_from_slice(&value_hash);
+
+    res
+}
+
+/// Computes the Merkle root from the leaf and the proof.
+/// Every pair of nodes is hashed in sorted order, so the proof doesn't need the leaf position.
+pub(crate) fn merkle_proof_root(leaf: CryptoHash, proof: &[Base58CryptoHash]) -> CryptoHash {
+    proof.iter().fold(leaf, |node, sibling| {
+        let sibling: CryptoHash = (*sibling).into();
+        let (left, right) = if node <= sibling {
+            (node, sibling)
+        } else {
+            (sibling, node)
+        };
+        let value_hash = env::sha256(&[&[MERKLE_NODE_PREFIX][..], &left, &right].concat());
+        let mut res = CryptoHash::default();
+        res.copy_from_slice(&value_hash);
+
+        res
+    })
+}
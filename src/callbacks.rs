@@ -10,6 +10,22 @@ trait SelfCallbacks {
     fn after_lockup_termination(
         &mut self,
         account_id: AccountId,
+        lockup_terminations: Vec<LockupTermination>,
+    ) -> WrappedBalance;
+
+    fn after_draft_refunds_withdrawal(
+        &mut self,
+        account_id: AccountId,
+        amount: WrappedBalance,
+    ) -> WrappedBalance;
+
+    fn continue_draft_group_conversion(&mut self, draft_group_id: DraftGroupIndex);
+
+    fn after_token_balance_check(&mut self, obligations: WrappedBalance) -> bool;
+
+    fn after_airdrop_reclaim(
+        &mut self,
+        airdrop_id: AirdropIndex,
         amount: WrappedBalance,
     ) -> WrappedBalance;
 }
@@ -37,6 +53,7 @@ impl SelfCallbacks for Contract {
                 }
                 total_balance += claim_amount.0;
             }
+            self.stats.total_claimed += total_balance;
             if !remove_indices.is_empty() {
                 let mut indices = self.account_lockups.get(&account_id).unwrap_or_default();
                 for index in remove_indices {
@@ -57,9 +74,10 @@ impl SelfCallbacks for Contract {
                 if indices.insert(index) {
                     modified = true;
                 }
-                let mut lockup = self.lockups.get(index as _).unwrap();
+                let mut lockup = self.internal_get_lockup(index).unwrap();
                 lockup.claimed_balance -= claim_amount.0;
-                self.lockups.replace(index as _, &lockup);
+                self.internal_save_lockup(index, &lockup);
+                self.internal_add_lockup_event(index, LockupEventKind::Refund, claim_amount.0);
             }
 
             if modified {
@@ -73,23 +91,136 @@ impl SelfCallbacks for Contract {
     fn after_lockup_termination(
         &mut self,
         account_id: AccountId,
-        amount: WrappedBalance,
+        lockup_terminations: Vec<LockupTermination>,
     ) -> WrappedBalance {
         let promise_success = is_promise_success();
         if !promise_success {
             log!("Lockup termination transfer has failed.");
-            // There is no internal balance, so instead we create a new lockup.
-            let lockup = Lockup::new_unlocked(account_id, amount.0);
-            let lockup_index = self.internal_add_lockup(&lockup);
+            // There is no internal balance, so instead we restore the terminated lockups.
+            for LockupTermination {
+                index,
+                schedule,
+                termination_config,
+                template_id,
+                unvested_balance,
+            } in lockup_terminations
+            {
+                let mut lockup = self.internal_get_lockup(index).unwrap();
+                lockup.schedule = schedule;
+                lockup.template_id = template_id;
+                self.internal_add_beneficiary_lockup(
+                    termination_config.beneficiary_id.as_ref(),
+                    index,
+                );
+                lockup.termination_config = Some(termination_config);
+                self.internal_save_lockup(index, &lockup);
+                self.internal_add_lockup_event(index, LockupEventKind::Refund, unvested_balance.0);
+
+                let lockup_account_id: AccountId = lockup.account_id.into();
+                let mut indices = self
+                    .account_lockups
+                    .get(&lockup_account_id)
+                    .unwrap_or_default();
+                if indices.insert(index) {
+                    self.internal_save_account_lockups(&lockup_account_id, indices);
+                }
+                log!(
+                    "Restored lockup #{} for account {} after the failed refund of {} to {}",
+                    index,
+                    lockup_account_id,
+                    unvested_balance.0,
+                    account_id,
+                );
+            }
+            0.into()
+        } else {
+            let total_unvested_balance = lockup_terminations
+                .iter()
+                .map(|lockup_termination| lockup_termination.unvested_balance.0)
+                .sum::<Balance>();
+            self.stats.total_terminated += total_unvested_balance;
+            total_unvested_balance.into()
+        }
+    }
+
+    #[private]
+    fn after_draft_refunds_withdrawal(
+        &mut self,
+        account_id: AccountId,
+        amount: WrappedBalance,
+    ) -> WrappedBalance {
+        let promise_success = is_promise_success();
+        if promise_success {
+            self.stats.total_draft_refunded += amount.0;
+            amount
+        } else {
+            log!("Draft refunds withdrawal by {} has failed.", account_id);
+            self.internal_add_draft_refund(&account_id, amount.0);
+            0.into()
+        }
+    }
+
+    #[private]
+    fn continue_draft_group_conversion(&mut self, draft_group_id: DraftGroupIndex) {
+        self.internal_auto_convert_drafts(draft_group_id);
+    }
+
+    #[private]
+    fn after_token_balance_check(&mut self, obligations: WrappedBalance) -> bool {
+        let token_balance: Balance = match env::promise_result(0) {
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<WrappedBalance>(&value)
+                    .expect("Invalid token balance")
+                    .0
+            }
+            _ => env::panic(b"Token balance request has failed"),
+        };
+        let obligations = obligations.0;
+        let total_value_locked = self.stats.total_value_locked();
+        if total_value_locked != obligations {
             log!(
-                "Generated a new lockup #{} as a refund of {} for account {}",
-                lockup_index,
-                amount.0,
-                lockup.account_id.as_ref(),
+                "The total value locked {} doesn't match the obligations {}",
+                total_value_locked,
+                obligations,
             );
-            0.into()
+        }
+        if token_balance < obligations {
+            log!(
+                "Token balance {} is less than the obligations {}, the discrepancy is {}",
+                token_balance,
+                obligations,
+                obligations - token_balance,
+            );
+            false
         } else {
+            if token_balance > obligations {
+                log!(
+                    "Token balance {} exceeds the obligations {} by {}",
+                    token_balance,
+                    obligations,
+                    token_balance - obligations,
+                );
+            }
+            true
+        }
+    }
+
+    #[private]
+    fn after_airdrop_reclaim(
+        &mut self,
+        airdrop_id: AirdropIndex,
+        amount: WrappedBalance,
+    ) -> WrappedBalance {
+        let promise_success = is_promise_success();
+        if promise_success {
+            self.stats.total_airdrop_reclaimed += amount.0;
             amount
+        } else {
+            log!("Airdrop {} reclaim has failed.", airdrop_id);
+            let mut airdrop = self.airdrops.get(airdrop_id as _).unwrap();
+            airdrop.reclaimed = false;
+            self.airdrops.replace(airdrop_id as _, &airdrop);
+            0.into()
         }
     }
 }
@@ -9,6 +9,11 @@ pub type DraftIndex = u32;
 pub struct Draft {
     pub draft_group_id: DraftGroupIndex,
     pub lockup_create: LockupCreate,
+    #[serde(default)]
+    pub metadata: Option<DraftMetadata>,
+    /// The timestamp when the draft was created. It's set by the contract.
+    #[serde(default)]
+    pub created_at: TimestampSec,
 }
 
 impl Draft {
@@ -25,24 +30,194 @@ impl Draft {
     }
 }
 
+/// The stored form of a draft.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum StoredDraft {
+    Draft(Draft),
+    Template(TemplateDraft),
+}
+
+impl StoredDraft {
+    pub fn draft_group_id(&self) -> DraftGroupIndex {
+        match self {
+            Self::Draft(draft) => draft.draft_group_id,
+            Self::Template(draft) => draft.draft_group_id,
+        }
+    }
+
+    pub fn set_draft_group_id(&mut self, draft_group_id: DraftGroupIndex) {
+        match self {
+            Self::Draft(draft) => draft.draft_group_id = draft_group_id,
+            Self::Template(draft) => draft.draft_group_id = draft_group_id,
+        }
+    }
+
+    /// The lockup receiver of the draft.
+    pub fn account_id(&self) -> &ValidAccountId {
+        match self {
+            Self::Draft(draft) => &draft.lockup_create.account_id,
+            Self::Template(draft) => &draft.lockup_create.account_id,
+        }
+    }
+
+    pub fn total_balance(&self) -> Balance {
+        match self {
+            Self::Draft(draft) => draft.total_balance(),
+            Self::Template(draft) => draft.lockup_create.amount.0,
+        }
+    }
+}
+
+/// Optional labels of a draft group or a draft to match them with external records.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub struct DraftMetadata {
+    pub name: Option<String>,
+    pub memo: Option<String>,
+    /// The ID of the record in an external system, e.g. a payroll batch.
+    pub external_id: Option<String>,
+}
+
+/// Defines who can convert drafts of a funded draft group into lockups.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub enum DraftConversionPolicy {
+    /// Anyone can convert drafts.
+    Anyone,
+    /// Only the lockup receiver of the draft can convert it.
+    Recipient,
+    /// Only the draft group creator and operators can convert drafts.
+    Operators,
+}
+
+/// Requires every draft of the group to be accepted by its receiver before the conversion.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub struct DraftAcceptance {
+    /// An optional hash of the terms the receiver has to submit to accept the draft.
+    pub terms_hash: Option<Base58CryptoHash>,
+    /// The timestamp after which unaccepted drafts can be reclaimed by the payer.
+    pub expiration_timestamp: TimestampSec,
+}
+
+/// Draft groups matching all the given fields are returned by `get_draft_groups_filtered`.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+pub struct DraftGroupFilter {
+    pub creator_id: Option<ValidAccountId>,
+    pub name: Option<String>,
+    pub external_id: Option<String>,
+    pub created_from: Option<TimestampSec>,
+    pub created_to: Option<TimestampSec>,
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct DraftGroup {
     pub total_amount: Balance,
+    /// The account that completed the funding of the draft group.
+    /// It's the beneficiary of the terminated lockups created from the drafts,
+    /// unless the `beneficiary_id` is set.
     pub payer_id: Option<ValidAccountId>,
+    /// The creator and the termination beneficiary of the lockups created from the drafts.
+    /// Required for the draft group funded by multiple payers.
+    pub beneficiary_id: Option<ValidAccountId>,
+    /// The amounts transferred by each payer. The draft group is funded once their sum reaches
+    /// the total amount. Refunds are split between the payers pro rata to their contributions.
+    pub contributions: HashMap<AccountId, Balance>,
+    pub funded_amount: Balance,
     pub draft_indices: HashSet<DraftIndex>,
     pub discarded: bool,
+    /// The account ID who created the draft group.
+    pub creator_id: ValidAccountId,
+    /// Account IDs that can manage the draft group along with the creator.
+    pub operators: HashSet<AccountId>,
+    pub conversion_policy: DraftConversionPolicy,
+    pub acceptance: Option<DraftAcceptance>,
+    pub accepted_draft_indices: HashSet<DraftIndex>,
+    /// The timestamp after which the payer can cancel the funded draft group.
+    /// The funded draft group cannot be cancelled if not set.
+    pub cancellable_after: Option<TimestampSec>,
+    pub metadata: Option<DraftMetadata>,
+    pub created_at: TimestampSec,
+    /// The timestamp after which the not funded draft group expires and can be cleaned up.
+    pub funding_deadline: Option<TimestampSec>,
+    /// Whether to convert all drafts into lockups right after the draft group is funded.
+    pub auto_convert: bool,
 }
 
 impl DraftGroup {
-    pub fn new() -> Self {
+    pub fn new(creator_id: ValidAccountId, metadata: Option<DraftMetadata>) -> Self {
         Self {
             total_amount: 0,
             payer_id: None,
+            beneficiary_id: None,
+            contributions: HashMap::new(),
+            funded_amount: 0,
             draft_indices: HashSet::new(),
             discarded: false,
+            creator_id,
+            operators: HashSet::new(),
+            conversion_policy: DraftConversionPolicy::Anyone,
+            acceptance: None,
+            accepted_draft_indices: HashSet::new(),
+            cancellable_after: None,
+            metadata,
+            created_at: current_timestamp_sec(),
+            funding_deadline: None,
+            auto_convert: false,
         }
     }
 
+    pub fn matches(&self, filter: &DraftGroupFilter) -> bool {
+        let metadata = self.metadata.clone().unwrap_or_default();
+        filter
+            .creator_id
+            .iter()
+            .all(|creator_id| creator_id == &self.creator_id)
+            && filter
+                .name
+                .iter()
+                .all(|name| metadata.name.as_ref() == Some(name))
+            && filter
+                .external_id
+                .iter()
+                .all(|external_id| metadata.external_id.as_ref() == Some(external_id))
+            && filter
+                .created_from
+                .iter()
+                .all(|created_from| self.created_at >= *created_from)
+            && filter
+                .created_to
+                .iter()
+                .all(|created_to| self.created_at <= *created_to)
+    }
+
+    pub fn assert_creator(&self, account_id: &AccountId) {
+        assert_eq!(
+            self.creator_id.as_ref(),
+            account_id,
+            "Not the draft group creator"
+        );
+    }
+
+    pub fn assert_payer(&self, account_id: &AccountId) {
+        assert!(
+            self.contributions.contains_key(account_id),
+            "Not the draft group payer"
+        );
+    }
+
+    pub fn assert_operator(&self, account_id: &AccountId) {
+        assert!(
+            self.creator_id.as_ref() == account_id || self.operators.contains(account_id),
+            "Not a draft group operator"
+        );
+    }
+
     pub fn assert_can_add_draft(&self) {
         assert!(
             !self.discarded,
@@ -52,6 +227,10 @@ impl DraftGroup {
             self.payer_id.is_none(),
             "cannot add draft, group already funded"
         );
+        assert_eq!(
+            self.funded_amount, 0,
+            "cannot add draft, group is partially funded"
+        );
     }
 
     pub fn assert_can_convert_draft(&self) {
@@ -65,17 +244,278 @@ impl DraftGroup {
         );
     }
 
+    pub fn assert_can_convert_draft_by(&self, draft: &StoredDraft, account_id: &AccountId) {
+        match self.conversion_policy {
+            DraftConversionPolicy::Anyone => {}
+            DraftConversionPolicy::Recipient => assert_eq!(
+                draft.account_id().as_ref(),
+                account_id,
+                "Only the draft recipient can convert the draft"
+            ),
+            DraftConversionPolicy::Operators => self.assert_operator(account_id),
+        }
+    }
+
+    pub fn assert_can_update(&self) {
+        assert!(
+            !self.discarded,
+            "cannot update draft group, draft group is discarded"
+        );
+        assert!(
+            self.payer_id.is_none(),
+            "cannot update draft group, group already funded"
+        );
+        assert_eq!(
+            self.funded_amount, 0,
+            "cannot update draft group, group is partially funded"
+        );
+    }
+
+    pub fn add_draft(&mut self, draft_id: DraftIndex, amount: Balance) {
+        self.total_amount = self
+            .total_amount
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+        self.draft_indices.insert(draft_id);
+    }
+
+    /// Removes the draft from the group. The acceptance of the draft is revoked.
+    pub fn remove_draft(&mut self, draft_id: DraftIndex, amount: Balance) {
+        assert!(self.total_amount >= amount, "Invariant");
+        self.total_amount -= amount;
+        assert!(self.draft_indices.remove(&draft_id), "Invariant");
+        self.accepted_draft_indices.remove(&draft_id);
+    }
+
+    pub fn set_conversion_policy(&mut self, conversion_policy: DraftConversionPolicy) {
+        self.assert_can_update();
+        self.conversion_policy = conversion_policy;
+        self.assert_valid_auto_convert();
+    }
+
+    pub fn set_funding_deadline(&mut self, funding_deadline: Option<TimestampSec>) {
+        self.assert_can_update();
+        if let Some(funding_deadline) = funding_deadline {
+            assert!(
+                funding_deadline > current_timestamp_sec(),
+                "expected funding_deadline > now"
+            );
+        }
+        self.funding_deadline = funding_deadline;
+    }
+
+    pub fn set_auto_convert(&mut self, auto_convert: bool) {
+        self.assert_can_update();
+        self.auto_convert = auto_convert;
+        self.assert_valid_auto_convert();
+    }
+
+    /// The automatic conversion is done on behalf of the payer, so it's allowed only if
+    /// anyone can convert drafts.
+    pub fn assert_valid_auto_convert(&self) {
+        assert!(
+            !self.auto_convert || self.conversion_policy == DraftConversionPolicy::Anyone,
+            "The auto conversion requires the Anyone conversion policy"
+        );
+    }
+
+    pub fn set_beneficiary(&mut self, beneficiary_id: Option<ValidAccountId>) {
+        self.assert_can_update();
+        self.beneficiary_id = beneficiary_id;
+    }
+
+    /// Returns the creator and the termination beneficiary of the lockups created from
+    /// the drafts or `None` if the draft group is not funded.
+    pub fn lockup_creator_id(&self) -> Option<ValidAccountId> {
+        self.payer_id
+            .as_ref()
+            .map(|payer_id| self.beneficiary_id.as_ref().unwrap_or(payer_id).clone())
+    }
+
+    /// Returns sorted IDs of drafts that don't require an acceptance or are already accepted.
+    pub fn auto_convertible_draft_ids(&self) -> Vec<DraftIndex> {
+        let mut draft_ids: Vec<DraftIndex> = self
+            .draft_indices
+            .iter()
+            .filter(|draft_id| {
+                self.acceptance.is_none() || self.accepted_draft_indices.contains(draft_id)
+            })
+            .cloned()
+            .collect();
+        draft_ids.sort_unstable();
+        draft_ids
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.payer_id.is_none()
+            && self
+                .funding_deadline
+                .iter()
+                .any(|funding_deadline| current_timestamp_sec() > *funding_deadline)
+    }
+
+    pub fn set_acceptance(&mut self, acceptance: Option<DraftAcceptance>) {
+        self.assert_can_update();
+        if let Some(acceptance) = &acceptance {
+            assert!(
+                acceptance.expiration_timestamp > current_timestamp_sec(),
+                "expected expiration_timestamp > now"
+            );
+        }
+        self.acceptance = acceptance;
+    }
+
+    pub fn accept_draft(&mut self, draft_id: DraftIndex, terms_hash: Option<Base58CryptoHash>) {
+        assert!(
+            !self.discarded,
+            "cannot accept draft, draft group is discarded"
+        );
+        let acceptance = self
+            .acceptance
+            .as_ref()
+            .expect("draft acceptance is not required");
+        assert!(
+            current_timestamp_sec() <= acceptance.expiration_timestamp,
+            "draft acceptance has expired"
+        );
+        if let Some(expected_terms_hash) = &acceptance.terms_hash {
+            let expected_terms_hash: CryptoHash = (*expected_terms_hash).into();
+            let terms_hash: CryptoHash = terms_hash.expect("terms hash required").into();
+            assert_eq!(
+                expected_terms_hash, terms_hash,
+                "The terms hash doesn't match"
+            );
+        }
+        assert!(
+            self.accepted_draft_indices.insert(draft_id),
+            "draft already accepted"
+        );
+    }
+
+    pub fn assert_draft_accepted(&self, draft_id: DraftIndex) {
+        if self.acceptance.is_some() {
+            assert!(
+                self.accepted_draft_indices.contains(&draft_id),
+                "draft is not accepted"
+            );
+        }
+    }
+
+    pub fn assert_can_reclaim_draft(&self, draft_id: DraftIndex) {
+        assert!(
+            self.payer_id.is_some(),
+            "cannot reclaim draft from not funded group"
+        );
+        if self.discarded {
+            // The funded draft group has been cancelled by the payer.
+            return;
+        }
+        let acceptance = self
+            .acceptance
+            .as_ref()
+            .expect("draft acceptance is not required");
+        assert!(
+            current_timestamp_sec() > acceptance.expiration_timestamp,
+            "draft acceptance has not expired yet"
+        );
+        assert!(
+            !self.accepted_draft_indices.contains(&draft_id),
+            "cannot reclaim accepted draft"
+        );
+    }
+
     pub fn assert_can_fund(&self) {
         assert!(
             !self.discarded,
             "cannot fund draft, draft group is discarded"
         );
         assert!(self.payer_id.is_none(), "draft group already funded");
+        assert!(
+            !self.is_expired(),
+            "cannot fund draft, draft group funding deadline has passed"
+        );
     }
 
-    pub fn fund(&mut self, payer_id: &ValidAccountId) {
+    /// Adds the contribution of the payer. The cancellation timelock is set by the first payer,
+    /// the following payers can only confirm it.
+    pub fn fund(
+        &mut self,
+        payer_id: &ValidAccountId,
+        amount: Balance,
+        cancellable_after: Option<TimestampSec>,
+    ) {
         self.assert_can_fund();
-        self.payer_id = Some(payer_id.clone());
+        assert!(amount > 0, "expected amount to be positive");
+        let funded_amount = self
+            .funded_amount
+            .checked_add(amount)
+            .expect("attempt to add with overflow");
+        assert!(
+            funded_amount <= self.total_amount,
+            "The transferred balance exceeds the remaining draft group balance"
+        );
+        if self.funded_amount == 0 {
+            self.cancellable_after = cancellable_after;
+        } else if cancellable_after.is_some() {
+            assert_eq!(
+                cancellable_after, self.cancellable_after,
+                "The cancellable_after doesn't match the draft group"
+            );
+        }
+        self.funded_amount = funded_amount;
+        *self
+            .contributions
+            .entry(payer_id.as_ref().clone())
+            .or_default() += amount;
+        assert!(
+            self.contributions.len() == 1 || self.beneficiary_id.is_some(),
+            "The draft group funded by multiple payers requires the beneficiary_id"
+        );
+        if funded_amount == self.total_amount {
+            self.payer_id = Some(payer_id.clone());
+        }
+    }
+
+    /// Splits the refunded amount between the payers pro rata to their contributions.
+    /// The rounding remainder goes to the given account.
+    pub fn refund_shares(
+        &self,
+        amount: Balance,
+        account_id: &AccountId,
+    ) -> Vec<(AccountId, Balance)> {
+        let mut remaining_amount = amount;
+        let mut shares: Vec<(AccountId, Balance)> = self
+            .contributions
+            .iter()
+            .map(|(payer_id, contribution)| {
+                let share = (U256::from(amount) * U256::from(*contribution)
+                    / U256::from(self.funded_amount))
+                .as_u128();
+                remaining_amount -= share;
+                (payer_id.clone(), share)
+            })
+            .collect();
+        shares.push((account_id.clone(), remaining_amount));
+        shares
+    }
+
+    pub fn cancel(&mut self) {
+        assert!(
+            !self.discarded,
+            "cannot cancel, draft group already discarded"
+        );
+        assert!(
+            self.payer_id.is_some(),
+            "cannot cancel not funded draft group"
+        );
+        let cancellable_after = self
+            .cancellable_after
+            .expect("draft group is not cancellable");
+        assert!(
+            current_timestamp_sec() >= cancellable_after,
+            "draft group cannot be cancelled yet"
+        );
+        self.discarded = true;
     }
 
     pub fn assert_can_discard(&mut self) {
@@ -89,9 +529,12 @@ impl DraftGroup {
         );
     }
 
-    pub fn discard(&mut self) {
+    /// Discards the draft group and returns the contributions of the partial funding.
+    pub fn discard(&mut self) -> Vec<(AccountId, Balance)> {
         self.assert_can_discard();
         self.discarded = true;
+        self.funded_amount = 0;
+        self.contributions.drain().collect()
     }
 
     pub fn assert_can_delete_draft(&mut self) {
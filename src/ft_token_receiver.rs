@@ -4,6 +4,15 @@ use crate::*;
 #[serde(crate = "near_sdk::serde")]
 pub struct DraftGroupFunding {
     pub draft_group_id: DraftGroupIndex,
+    /// An optional timestamp after which the payer can cancel the draft group
+    /// and get back the balance of unconverted drafts. It's set by the first payer.
+    pub cancellable_after: Option<TimestampSec>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AirdropFunding {
+    pub airdrop_id: AirdropIndex,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -11,7 +20,10 @@ pub struct DraftGroupFunding {
 #[serde(untagged)]
 pub enum FtMessage {
     LockupCreate(LockupCreate),
+    FractionalLockupCreate(FractionalLockupCreate),
+    TemplateLockupCreate(TemplateLockupCreate),
     DraftGroupFunding(DraftGroupFunding),
+    AirdropFunding(AirdropFunding),
 }
 
 #[near_bindgen]
@@ -29,6 +41,7 @@ impl FungibleTokenReceiver for Contract {
         );
         let amount = amount.into();
         self.assert_deposit_whitelist(sender_id.as_ref());
+        self.stats.total_deposited += amount;
 
         let ft_message: FtMessage = serde_json::from_str(&msg).unwrap();
         match ft_message {
@@ -42,19 +55,64 @@ impl FungibleTokenReceiver for Contract {
                     index
                 );
             }
+            FtMessage::FractionalLockupCreate(fractional_lockup_create) => {
+                let lockup = fractional_lockup_create
+                    .into_lockup_create(amount)
+                    .into_lockup(&sender_id);
+                lockup.assert_new_valid(amount);
+                let index = self.internal_add_lockup(&lockup);
+                log!(
+                    "Created new lockup for {} with index {}",
+                    lockup.account_id.as_ref(),
+                    index
+                );
+            }
+            FtMessage::TemplateLockupCreate(template_lockup_create) => {
+                let template =
+                    self.internal_get_schedule_template(&template_lockup_create.template_id);
+                let lockup = template_lockup_create.into_lockup(&template, &sender_id);
+                lockup.assert_new_valid(amount);
+                let index = self.internal_add_lockup(&lockup);
+                log!(
+                    "Created new lockup for {} with index {} from template {}",
+                    lockup.account_id.as_ref(),
+                    index,
+                    template_lockup_create.template_id,
+                );
+            }
             FtMessage::DraftGroupFunding(funding) => {
                 let draft_group_id = funding.draft_group_id;
                 let mut draft_group = self
                     .draft_groups
                     .get(&draft_group_id as _)
                     .expect("draft group not found");
+                draft_group.fund(&sender_id, amount, funding.cancellable_after);
+                self.draft_groups.insert(&draft_group_id as _, &draft_group);
+                if draft_group.payer_id.is_some() {
+                    log!("Funded draft group {}", draft_group_id);
+                    self.internal_auto_convert_drafts(draft_group_id);
+                } else {
+                    log!(
+                        "Partially funded draft group {} with {} out of {}",
+                        draft_group_id,
+                        draft_group.funded_amount,
+                        draft_group.total_amount,
+                    );
+                }
+            }
+            FtMessage::AirdropFunding(funding) => {
+                let airdrop_id = funding.airdrop_id;
+                let mut airdrop = self
+                    .airdrops
+                    .get(airdrop_id as _)
+                    .expect("airdrop not found");
                 assert_eq!(
-                    draft_group.total_amount, amount,
-                    "The draft group total balance doesn't match the transferred balance",
+                    airdrop.total_amount, amount,
+                    "The airdrop total amount doesn't match the transferred balance",
                 );
-                draft_group.fund(&sender_id);
-                self.draft_groups.insert(&draft_group_id as _, &draft_group);
-                log!("Funded draft group {}", draft_group_id);
+                airdrop.fund(&sender_id);
+                self.airdrops.replace(airdrop_id as _, &airdrop);
+                log!("Funded airdrop {}", airdrop_id);
             }
         }
 
@@ -0,0 +1,24 @@
+use crate::*;
+
+/// The maximum number of events stored per lockup by default.
+pub const DEFAULT_LOCKUP_HISTORY_LIMIT: u32 = 20;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub enum LockupEventKind {
+    Claim,
+    /// The claimed or terminated balance returned to the lockup after the failed transfer.
+    Refund,
+    Termination,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+pub struct LockupEvent {
+    pub timestamp: TimestampSec,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    pub kind: LockupEventKind,
+}
@@ -8,27 +8,289 @@ impl Contract {
         );
     }
 
+    pub(crate) fn internal_get_schedule_template(
+        &self,
+        template_id: &TemplateId,
+    ) -> ScheduleTemplate {
+        self.schedule_templates
+            .get(template_id)
+            .expect("schedule template not found")
+    }
+
+    /// Returns the lockup with the schedules resolved from its template.
+    pub(crate) fn internal_get_lockup(&self, index: LockupIndex) -> Option<Lockup> {
+        self.lockups.get(index as _).map(|lockup| match lockup {
+            StoredLockup::Lockup(lockup) => lockup,
+            StoredLockup::Template(lockup) => {
+                let template = self
+                    .schedule_templates
+                    .get(&lockup.template_id)
+                    .expect("Invariant");
+                lockup.into_lockup(&template)
+            }
+        })
+    }
+
+    pub(crate) fn internal_save_lockup(&mut self, index: LockupIndex, lockup: &Lockup) {
+        self.lockups.replace(index as _, &lockup.into());
+    }
+
     pub(crate) fn internal_add_lockup(&mut self, lockup: &Lockup) -> LockupIndex {
         let index = self.lockups.len() as LockupIndex;
-        self.lockups.push(lockup);
+        self.lockups.push(&lockup.into());
         let mut indices = self
             .account_lockups
             .get(lockup.account_id.as_ref())
             .unwrap_or_default();
         indices.insert(index);
         self.internal_save_account_lockups(lockup.account_id.as_ref(), indices);
+
+        let creator_id = lockup.creator_id.as_ref();
+        let mut indices = self.creator_lockups.get(creator_id).unwrap_or_else(|| {
+            Vector::new(StorageKey::CreatorLockupsByAccount {
+                account_hash: hash_account_id(creator_id),
+            })
+        });
+        indices.push(&index);
+        self.creator_lockups.insert(creator_id, &indices);
+
+        if let Some(termination_config) = &lockup.termination_config {
+            self.internal_add_beneficiary_lockup(termination_config.beneficiary_id.as_ref(), index);
+        }
         index
     }
 
+    pub(crate) fn internal_add_beneficiary_lockup(
+        &mut self,
+        beneficiary_id: &AccountId,
+        index: LockupIndex,
+    ) {
+        let mut indices = self
+            .beneficiary_lockups
+            .get(beneficiary_id)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(StorageKey::BeneficiaryLockupsByAccount {
+                    account_hash: hash_account_id(beneficiary_id),
+                })
+            });
+        indices.insert(&index);
+        self.beneficiary_lockups.insert(beneficiary_id, &indices);
+    }
+
+    pub(crate) fn internal_remove_beneficiary_lockup(
+        &mut self,
+        beneficiary_id: &AccountId,
+        index: LockupIndex,
+    ) {
+        let mut indices = match self.beneficiary_lockups.get(beneficiary_id) {
+            Some(indices) => indices,
+            None => return,
+        };
+        indices.remove(&index);
+        if indices.is_empty() {
+            self.beneficiary_lockups.remove(beneficiary_id);
+        } else {
+            self.beneficiary_lockups.insert(beneficiary_id, &indices);
+        }
+    }
+
+    /// Saves the active lockups of the account. The removed lockups are moved to the archive,
+    /// the restored lockups are taken out of it.
     pub(crate) fn internal_save_account_lockups(
         &mut self,
         account_id: &AccountId,
         indices: HashSet<LockupIndex>,
     ) {
+        let old_indices = if indices.is_empty() {
+            self.account_lockups.remove(account_id)
+        } else {
+            self.account_lockups.insert(account_id, &indices)
+        }
+        .unwrap_or_default();
+        self.stats
+            .on_account_lockups_update(old_indices.len(), indices.len());
+
+        let mut archived_indices: Vec<LockupIndex> =
+            old_indices.difference(&indices).cloned().collect();
+        archived_indices.sort_unstable();
+        let restored_indices: Vec<LockupIndex> =
+            indices.difference(&old_indices).cloned().collect();
+        let mut archive = match self.account_archived_lockups.get(account_id) {
+            Some(archive) => archive,
+            None if archived_indices.is_empty() => return,
+            None => UnorderedSet::new(StorageKey::AccountArchivedLockupsByAccount {
+                account_hash: hash_account_id(account_id),
+            }),
+        };
+        let mut modified = false;
+        for index in archived_indices {
+            modified |= archive.insert(&index);
+        }
+        for index in restored_indices {
+            modified |= archive.remove(&index);
+        }
+        if !modified {
+            return;
+        }
+        if archive.is_empty() {
+            self.account_archived_lockups.remove(account_id);
+        } else {
+            self.account_archived_lockups.insert(account_id, &archive);
+        }
+    }
+
+    /// The unclaimed balance of the lockups in the range.
+    pub(crate) fn internal_lockups_balance(&self, range: std::ops::Range<u64>) -> Balance {
+        range
+            .map(|index| {
+                let lockup = self.internal_get_lockup(index as _).unwrap();
+                lockup.schedule.total_balance() - lockup.claimed_balance
+            })
+            .sum()
+    }
+
+    /// The balance of funded and partially funded draft groups in the range.
+    pub(crate) fn internal_draft_groups_balance(&self, range: std::ops::Range<u64>) -> Balance {
+        let draft_groups = self.draft_groups.values_as_vector();
+        range
+            .map(|index| {
+                let draft_group = draft_groups.get(index).unwrap();
+                if draft_group.payer_id.is_some() {
+                    draft_group.total_amount
+                } else {
+                    draft_group.funded_amount
+                }
+            })
+            .sum()
+    }
+
+    /// The unclaimed balance of funded airdrops in the range.
+    pub(crate) fn internal_airdrops_balance(&self, range: std::ops::Range<u64>) -> Balance {
+        range
+            .map(|index| self.airdrops.get(index).unwrap().unclaimed_amount())
+            .sum()
+    }
+
+    /// The sum of all balances the contract owes. Unlike the total value locked it's computed
+    /// from the stored lockups, draft groups and airdrops, so it iterates all of them.
+    pub(crate) fn internal_total_obligations(&self) -> Balance {
+        self.internal_lockups_balance(0..self.lockups.len())
+            + self.internal_draft_groups_balance(0..self.draft_groups.len())
+            + self.internal_airdrops_balance(0..self.airdrops.len())
+            + self.stats.pending_draft_refunds
+    }
+
+    /// Appends the event to the lockup history, dropping the oldest events above the limit.
+    pub(crate) fn internal_add_lockup_event(
+        &mut self,
+        index: LockupIndex,
+        kind: LockupEventKind,
+        amount: Balance,
+    ) {
+        let limit = self.lockup_history_limit as usize;
+        if limit == 0 {
+            return;
+        }
+        let mut events = self.lockup_history.get(&index).unwrap_or_default();
+        events.push(LockupEvent {
+            timestamp: current_timestamp_sec(),
+            amount,
+            kind,
+        });
+        if events.len() > limit {
+            events.drain(..events.len() - limit);
+        }
+        self.lockup_history.insert(&index, &events);
+    }
+
+    /// Adds the validated drafts to their draft groups on behalf of `account_id`.
+    pub(crate) fn internal_create_drafts(
+        &mut self,
+        account_id: &AccountId,
+        drafts: Vec<StoredDraft>,
+    ) -> Vec<DraftIndex> {
+        let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
+        let draft_ids: Vec<DraftIndex> = drafts
+            .into_iter()
+            .map(|draft| {
+                let draft_group = draft_group_lookup
+                    .entry(draft.draft_group_id())
+                    .or_insert_with(|| {
+                        let draft_group = self
+                            .draft_groups
+                            .get(&draft.draft_group_id() as _)
+                            .expect("draft group not found");
+                        draft_group.assert_operator(account_id);
+                        draft_group
+                    });
+                draft_group.assert_can_add_draft();
+
+                let index = self.next_draft_id;
+                self.next_draft_id += 1;
+                assert!(
+                    self.internal_insert_draft(index, &draft).is_none(),
+                    "Invariant"
+                );
+                draft_group.add_draft(index, draft.total_balance());
+
+                index
+            })
+            .collect();
+
+        draft_group_lookup
+            .iter()
+            .for_each(|(draft_group_id, draft_group)| {
+                self.draft_groups.insert(&draft_group_id as _, &draft_group);
+            });
+
+        draft_ids
+    }
+
+    /// Inserts the draft and updates the index of drafts by the lockup receiver.
+    pub(crate) fn internal_insert_draft(
+        &mut self,
+        draft_id: DraftIndex,
+        draft: &StoredDraft,
+    ) -> Option<StoredDraft> {
+        let previous_draft = self.drafts.insert(&draft_id, draft);
+        if let Some(previous_draft) = &previous_draft {
+            self.internal_remove_account_draft(previous_draft.account_id().as_ref(), draft_id);
+        }
+        let account_id = draft.account_id().as_ref();
+        let mut indices = self.account_drafts.get(account_id).unwrap_or_default();
+        indices.insert(draft_id);
+        self.account_drafts.insert(account_id, &indices);
+        previous_draft
+    }
+
+    /// Removes the draft and updates the index of drafts by the lockup receiver.
+    pub(crate) fn internal_remove_draft(&mut self, draft_id: DraftIndex) -> Option<StoredDraft> {
+        let draft = self.drafts.remove(&draft_id);
+        if let Some(draft) = &draft {
+            self.internal_remove_account_draft(draft.account_id().as_ref(), draft_id);
+        }
+        draft
+    }
+
+    /// Returns the draft with the lockup schedules resolved from its template.
+    pub(crate) fn internal_get_draft(&self, draft_id: DraftIndex) -> Option<Draft> {
+        self.drafts.get(&draft_id).map(|draft| match draft {
+            StoredDraft::Draft(draft) => draft,
+            StoredDraft::Template(draft) => {
+                let template =
+                    self.internal_get_schedule_template(&draft.lockup_create.template_id);
+                draft.into_draft(&template)
+            }
+        })
+    }
+
+    fn internal_remove_account_draft(&mut self, account_id: &AccountId, draft_id: DraftIndex) {
+        let mut indices = self.account_drafts.get(account_id).unwrap_or_default();
+        indices.remove(&draft_id);
         if indices.is_empty() {
-            self.account_lockups.remove(account_id);
+            self.account_drafts.remove(account_id);
         } else {
-            self.account_lockups.insert(account_id, &indices);
+            self.account_drafts.insert(account_id, &indices);
         }
     }
 
@@ -40,7 +302,12 @@ impl Contract {
             .get(account_id)
             .unwrap_or_default()
             .into_iter()
-            .map(|lockup_index| (lockup_index, self.lockups.get(lockup_index as _).unwrap()))
+            .map(|lockup_index| {
+                (
+                    lockup_index,
+                    self.internal_get_lockup(lockup_index).unwrap(),
+                )
+            })
             .collect()
     }
 
@@ -59,9 +326,214 @@ impl Contract {
                     "lockup not found for account: {}",
                     lockup_index,
                 );
-                let lockup = self.lockups.get(lockup_index as _).unwrap();
+                let lockup = self.internal_get_lockup(lockup_index).unwrap();
                 (lockup_index.clone(), lockup)
             })
             .collect()
     }
+
+    pub(crate) fn internal_termination_timestamp(
+        termination_timestamp: Option<TimestampSec>,
+    ) -> TimestampSec {
+        let current_timestamp = current_timestamp_sec();
+        let termination_timestamp = termination_timestamp.unwrap_or(current_timestamp);
+        assert!(
+            termination_timestamp >= current_timestamp,
+            "expected termination_timestamp >= now",
+        );
+        termination_timestamp
+    }
+
+    /// Terminates the lockup on behalf of `account_id`.
+    /// Returns the unvested balance with the lockup state required to restore it.
+    pub(crate) fn internal_terminate(
+        &mut self,
+        account_id: &AccountId,
+        lockup_index: LockupIndex,
+        hashed_schedule: Option<Schedule>,
+        termination_timestamp: TimestampSec,
+    ) -> LockupTermination {
+        let mut lockup = self
+            .internal_get_lockup(lockup_index)
+            .expect("Lockup not found");
+        let schedule = lockup.schedule.clone();
+        let template_id = lockup.template_id.clone();
+        let termination_config = lockup
+            .termination_config
+            .clone()
+            .expect("No termination config");
+        let unvested_balance = lockup.terminate(account_id, hashed_schedule, termination_timestamp);
+        self.internal_save_lockup(lockup_index, &lockup);
+        self.internal_add_lockup_event(
+            lockup_index,
+            LockupEventKind::Termination,
+            unvested_balance,
+        );
+        // the termination config is taken by the termination
+        self.internal_remove_beneficiary_lockup(
+            termination_config.beneficiary_id.as_ref(),
+            lockup_index,
+        );
+
+        // no need to store empty lockup
+        if lockup.schedule.total_balance() == 0 {
+            let lockup_account_id: AccountId = lockup.account_id.into();
+            let mut indices = self
+                .account_lockups
+                .get(&lockup_account_id)
+                .unwrap_or_default();
+            indices.remove(&lockup_index);
+            self.internal_save_account_lockups(&lockup_account_id, indices);
+        }
+
+        LockupTermination {
+            index: lockup_index,
+            schedule,
+            termination_config,
+            template_id,
+            unvested_balance: unvested_balance.into(),
+        }
+    }
+
+    /// Converts the removed draft into a lockup funded by the draft group payer.
+    pub(crate) fn internal_convert_draft(
+        &mut self,
+        draft_id: DraftIndex,
+        draft: StoredDraft,
+        draft_group: &mut DraftGroup,
+    ) -> LockupIndex {
+        let payer_id = draft_group
+            .lockup_creator_id()
+            .expect("expected present payer_id");
+        draft_group.remove_draft(draft_id, draft.total_balance());
+
+        let lockup = match draft {
+            StoredDraft::Draft(draft) => draft.lockup_create.into_lockup(&payer_id),
+            StoredDraft::Template(draft) => {
+                let template =
+                    self.internal_get_schedule_template(&draft.lockup_create.template_id);
+                draft.lockup_create.into_lockup(&template, &payer_id)
+            }
+        };
+        let index = self.internal_add_lockup(&lockup);
+        log!(
+            "Created new lockup for {} with index {} from draft {}",
+            lockup.account_id.as_ref(),
+            index,
+            draft_id,
+        );
+
+        index
+    }
+
+    /// Converts the next batch of drafts of the funded draft group. If some drafts remain,
+    /// the conversion is continued by a call to itself with the remaining gas.
+    pub(crate) fn internal_auto_convert_drafts(&mut self, draft_group_id: DraftGroupIndex) {
+        let mut draft_group = match self.draft_groups.get(&draft_group_id as _) {
+            Some(draft_group) => draft_group,
+            None => return,
+        };
+        if !draft_group.auto_convert
+            || draft_group.conversion_policy != DraftConversionPolicy::Anyone
+            || draft_group.discarded
+            || draft_group.payer_id.is_none()
+        {
+            return;
+        }
+
+        let draft_ids: Vec<DraftIndex> = draft_group.auto_convertible_draft_ids();
+        let batch_size = std::cmp::min(draft_ids.len(), DRAFT_CONVERSION_BATCH_SIZE as usize);
+        for draft_id in &draft_ids[..batch_size] {
+            let draft = self.internal_remove_draft(*draft_id).expect("Invariant");
+            self.internal_convert_draft(*draft_id, draft, &mut draft_group);
+        }
+        let remaining = draft_ids.len() - batch_size;
+        log!(
+            "Converted {} drafts of draft group {}, {} remaining",
+            batch_size,
+            draft_group_id,
+            remaining,
+        );
+
+        if draft_group.draft_indices.is_empty() {
+            self.draft_groups.remove(&draft_group_id as _);
+        } else {
+            self.draft_groups.insert(&draft_group_id as _, &draft_group);
+        }
+
+        if remaining > 0 {
+            let gas = (env::prepaid_gas() - env::used_gas())
+                .saturating_sub(GAS_RESERVED_FOR_DRAFT_GROUP_CONVERSION);
+            assert!(
+                gas >= GAS_FOR_DRAFT_GROUP_CONVERSION,
+                "Not enough gas to continue the draft group conversion, {} TGas required",
+                GAS_FOR_DRAFT_GROUP_CONVERSION / 10u64.pow(12),
+            );
+            ext_self::continue_draft_group_conversion(
+                draft_group_id,
+                &env::current_account_id(),
+                NO_DEPOSIT,
+                gas,
+            );
+        }
+    }
+
+    /// Discards the draft group and credits the partial funding back to the payers.
+    pub(crate) fn internal_discard_draft_group(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        draft_group: &mut DraftGroup,
+    ) {
+        for (payer_id, contribution) in draft_group.discard() {
+            log!(
+                "Refunding {} of the partial funding of draft group {} to {}",
+                contribution,
+                draft_group_id,
+                payer_id,
+            );
+            self.internal_add_draft_refund(&payer_id, contribution);
+        }
+    }
+
+    pub(crate) fn internal_add_draft_refund(&mut self, account_id: &AccountId, amount: Balance) {
+        if amount == 0 {
+            return;
+        }
+        self.stats.pending_draft_refunds += amount;
+        let refund = self.draft_refunds.get(account_id).unwrap_or(0);
+        self.draft_refunds.insert(
+            account_id,
+            &refund
+                .checked_add(amount)
+                .expect("attempt to add with overflow"),
+        );
+    }
+
+    pub(crate) fn internal_withdraw_draft_refunds(
+        &mut self,
+        account_id: AccountId,
+    ) -> PromiseOrValue<WrappedBalance> {
+        let amount = self.draft_refunds.remove(&account_id).unwrap_or(0);
+        if amount == 0 {
+            return PromiseOrValue::Value(0.into());
+        }
+        self.stats.pending_draft_refunds -= amount;
+
+        ext_fungible_token::ft_transfer(
+            account_id.clone(),
+            amount.into(),
+            Some("Draft refunds withdrawal".to_string()),
+            &self.token_account_id,
+            ONE_YOCTO,
+            GAS_FOR_FT_TRANSFER,
+        )
+        .then(ext_self::after_draft_refunds_withdrawal(
+            account_id,
+            amount.into(),
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            GAS_FOR_AFTER_FT_TRANSFER,
+        ))
+        .into()
+    }
 }
@@ -4,28 +4,37 @@ use near_contract_standards::fungible_token::core_impl::ext_fungible_token;
 use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
 use near_sdk::borsh::maybestd::collections::{HashMap, HashSet};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LookupMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector};
 use near_sdk::json_types::{Base58CryptoHash, ValidAccountId, WrappedBalance, U128};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     assert_one_yocto, env, ext_contract, is_promise_success, log, near_bindgen, serde_json,
-    AccountId, Balance, BorshStorageKey, CryptoHash, Gas, PanicOnDefault, PromiseOrValue,
-    Timestamp,
+    AccountId, Balance, BorshStorageKey, CryptoHash, Gas, PanicOnDefault, Promise, PromiseOrValue,
+    PromiseResult, Timestamp,
 };
 
+pub mod airdrop;
 pub mod callbacks;
 pub mod draft;
 pub mod ft_token_receiver;
+pub mod history;
 pub mod internal;
 pub mod lockup;
+pub mod migration;
 pub mod schedule;
+pub mod stats;
+pub mod template;
 pub mod termination;
 pub mod util;
 pub mod view;
 
+use crate::airdrop::*;
 use crate::draft::*;
+use crate::history::*;
 use crate::lockup::*;
 use crate::schedule::*;
+use crate::stats::*;
+use crate::template::*;
 use crate::termination::*;
 use crate::util::*;
 
@@ -36,10 +45,26 @@ pub type TokenAccountId = AccountId;
 
 const GAS_FOR_FT_TRANSFER: Gas = 15_000_000_000_000;
 const GAS_FOR_AFTER_FT_TRANSFER: Gas = 20_000_000_000_000;
+/// The extra gas for the termination callback to restore each lockup if the refund fails.
+const GAS_FOR_AFTER_LOCKUP_TERMINATION_PER_LOCKUP: Gas = 10_000_000_000_000;
+/// The gas kept to finish the call that schedules the termination refund.
+const GAS_RESERVED_FOR_TERMINATION: Gas = 5_000_000_000_000;
+/// The minimum gas for the call converting the next batch of drafts.
+const GAS_FOR_DRAFT_GROUP_CONVERSION: Gas = 100_000_000_000_000;
+/// The gas kept to finish the call that schedules the next batch of drafts.
+const GAS_RESERVED_FOR_DRAFT_GROUP_CONVERSION: Gas = 10_000_000_000_000;
+const GAS_FOR_FT_BALANCE_OF: Gas = 10_000_000_000_000;
+const GAS_FOR_AFTER_TOKEN_BALANCE_CHECK: Gas = 10_000_000_000_000;
 
 const ONE_YOCTO: Balance = 1;
 const NO_DEPOSIT: Balance = 0;
 
+const DEFAULT_DRAFT_CLEANUP_LIMIT: u32 = 100;
+/// The maximum number of drafts converted automatically in one call.
+const DRAFT_CONVERSION_BATCH_SIZE: u32 = 20;
+/// The maximum number of lockups scanned by one `find_lockups` call.
+const LOCKUP_SEARCH_SCAN_LIMIT: u32 = 1000;
+
 uint::construct_uint! {
     pub struct U256(4);
 }
@@ -53,28 +78,67 @@ pub trait SelfCallbacks {
     ) -> WrappedBalance;
 
     fn after_lockup_termination(
+        &mut self,
+        account_id: AccountId,
+        lockup_terminations: Vec<LockupTermination>,
+    ) -> WrappedBalance;
+
+    fn after_draft_refunds_withdrawal(
         &mut self,
         account_id: AccountId,
         amount: WrappedBalance,
     ) -> WrappedBalance;
+
+    fn continue_draft_group_conversion(&mut self, draft_group_id: DraftGroupIndex);
+
+    fn after_token_balance_check(&mut self, obligations: WrappedBalance) -> bool;
+
+    fn after_airdrop_reclaim(
+        &mut self,
+        airdrop_id: AirdropIndex,
+        amount: WrappedBalance,
+    ) -> WrappedBalance;
 }
 
+/// The state of the initial release is converted by `migrate`.
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
     pub token_account_id: TokenAccountId,
 
-    pub lockups: Vector<Lockup>,
+    pub lockups: Vector<StoredLockup>,
 
     pub account_lockups: LookupMap<AccountId, HashSet<LockupIndex>>,
+    /// Fully claimed or terminated lockups that were removed from `account_lockups`.
+    pub account_archived_lockups: LookupMap<AccountId, UnorderedSet<LockupIndex>>,
+    /// The latest claims, refunds and terminations of every lockup.
+    pub lockup_history: LookupMap<LockupIndex, Vec<LockupEvent>>,
+    /// The maximum number of events kept per lockup, the history is disabled if it's 0.
+    pub lockup_history_limit: u32,
+    /// Lockups by the account that funded them, in ascending order.
+    pub creator_lockups: LookupMap<AccountId, Vector<LockupIndex>>,
+    /// Lockups by the beneficiary of their termination config.
+    pub beneficiary_lockups: LookupMap<AccountId, UnorderedSet<LockupIndex>>,
 
     /// Account IDs that can create new lockups.
     pub deposit_whitelist: UnorderedSet<AccountId>,
 
     pub next_draft_id: DraftIndex,
-    pub drafts: LookupMap<DraftIndex, Draft>,
+    pub drafts: LookupMap<DraftIndex, StoredDraft>,
     pub next_draft_group_id: DraftGroupIndex,
     pub draft_groups: UnorderedMap<DraftGroupIndex, DraftGroup>,
+    /// Not converted drafts by the lockup receiver.
+    pub account_drafts: LookupMap<AccountId, HashSet<DraftIndex>>,
+    /// Refunds of draft groups that are not yet withdrawn by the payers.
+    pub draft_refunds: LookupMap<AccountId, Balance>,
+
+    pub airdrops: Vector<Airdrop>,
+    /// Airdrop leaves that have been already converted into lockups.
+    pub airdrop_claims: LookupSet<(AirdropIndex, AccountId)>,
+
+    pub schedule_templates: UnorderedMap<TemplateId, ScheduleTemplate>,
+
+    pub stats: Stats,
 }
 
 #[derive(BorshStorageKey, BorshSerialize)]
@@ -84,6 +148,18 @@ pub(crate) enum StorageKey {
     DepositWhitelist,
     Drafts,
     DraftGroups,
+    Airdrops,
+    AirdropClaims,
+    ScheduleTemplates,
+    DraftRefunds,
+    AccountDrafts,
+    CreatorLockups,
+    BeneficiaryLockups,
+    AccountArchivedLockups,
+    LockupHistory,
+    CreatorLockupsByAccount { account_hash: CryptoHash },
+    BeneficiaryLockupsByAccount { account_hash: CryptoHash },
+    AccountArchivedLockupsByAccount { account_hash: CryptoHash },
 }
 
 #[near_bindgen]
@@ -95,12 +171,23 @@ impl Contract {
         Self {
             lockups: Vector::new(StorageKey::Lockups),
             account_lockups: LookupMap::new(StorageKey::AccountLockups),
+            account_archived_lockups: LookupMap::new(StorageKey::AccountArchivedLockups),
+            lockup_history: LookupMap::new(StorageKey::LockupHistory),
+            lockup_history_limit: DEFAULT_LOCKUP_HISTORY_LIMIT,
+            creator_lockups: LookupMap::new(StorageKey::CreatorLockups),
+            beneficiary_lockups: LookupMap::new(StorageKey::BeneficiaryLockups),
             token_account_id: token_account_id.into(),
             deposit_whitelist: deposit_whitelist_set,
             next_draft_id: 0,
             drafts: LookupMap::new(StorageKey::Drafts),
             next_draft_group_id: 0,
             draft_groups: UnorderedMap::new(StorageKey::DraftGroups),
+            account_drafts: LookupMap::new(StorageKey::AccountDrafts),
+            draft_refunds: LookupMap::new(StorageKey::DraftRefunds),
+            airdrops: Vector::new(StorageKey::Airdrops),
+            airdrop_claims: LookupSet::new(StorageKey::AirdropClaims),
+            schedule_templates: UnorderedMap::new(StorageKey::ScheduleTemplates),
+            stats: Stats::default(),
         }
     }
 
@@ -169,7 +256,12 @@ impl Contract {
                     lockup_index
                 );
                 total_claim_amount += lockup_claim.claim_amount.0;
-                self.lockups.replace(lockup_index as _, &lockup);
+                self.internal_save_lockup(lockup_index, lockup);
+                self.internal_add_lockup_event(
+                    lockup_index,
+                    LockupEventKind::Claim,
+                    lockup_claim.claim_amount.0,
+                );
                 lockup_claims.push(lockup_claim);
             }
         }
@@ -210,30 +302,14 @@ impl Contract {
     ) -> PromiseOrValue<WrappedBalance> {
         assert_one_yocto();
         let account_id = env::predecessor_account_id();
-        let mut lockup = self
-            .lockups
-            .get(lockup_index as _)
-            .expect("Lockup not found");
-        let current_timestamp = current_timestamp_sec();
-        let termination_timestamp = termination_timestamp.unwrap_or(current_timestamp);
-        assert!(
-            termination_timestamp >= current_timestamp,
-            "expected termination_timestamp >= now",
+        let termination_timestamp = Self::internal_termination_timestamp(termination_timestamp);
+        let lockup_termination = self.internal_terminate(
+            &account_id,
+            lockup_index,
+            hashed_schedule,
+            termination_timestamp,
         );
-        let unvested_balance =
-            lockup.terminate(&account_id, hashed_schedule, termination_timestamp);
-        self.lockups.replace(lockup_index as _, &lockup);
-
-        // no need to store empty lockup
-        if lockup.schedule.total_balance() == 0 {
-            let lockup_account_id: AccountId = lockup.account_id.into();
-            let mut indices = self
-                .account_lockups
-                .get(&lockup_account_id)
-                .unwrap_or_default();
-            indices.remove(&lockup_index);
-            self.internal_save_account_lockups(&lockup_account_id, indices);
-        }
+        let unvested_balance = lockup_termination.unvested_balance.0;
 
         if unvested_balance > 0 {
             ext_fungible_token::ft_transfer(
@@ -246,7 +322,7 @@ impl Contract {
             )
             .then(ext_self::after_lockup_termination(
                 account_id,
-                unvested_balance.into(),
+                vec![lockup_termination],
                 &env::current_account_id(),
                 NO_DEPOSIT,
                 GAS_FOR_AFTER_FT_TRANSFER,
@@ -257,6 +333,82 @@ impl Contract {
         }
     }
 
+    /// Terminates the lockups with a single refund transfer. The attached gas has to cover
+    /// the callback restoring each of the lockups in case the refund fails.
+    #[payable]
+    pub fn terminate_many(
+        &mut self,
+        lockups: Vec<(LockupIndex, Option<Schedule>)>,
+        termination_timestamp: Option<TimestampSec>,
+    ) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        assert!(!lockups.is_empty(), "expected at least one lockup");
+        let account_id = env::predecessor_account_id();
+        let termination_timestamp = Self::internal_termination_timestamp(termination_timestamp);
+        let num_lockups = lockups.len();
+        let mut total_unvested_balance: Balance = 0;
+        let mut lockup_terminations = vec![];
+        for (lockup_index, hashed_schedule) in lockups {
+            let lockup_termination = self.internal_terminate(
+                &account_id,
+                lockup_index,
+                hashed_schedule,
+                termination_timestamp,
+            );
+            if lockup_termination.unvested_balance.0 > 0 {
+                total_unvested_balance = total_unvested_balance
+                    .checked_add(lockup_termination.unvested_balance.0)
+                    .expect("attempt to add with overflow");
+                lockup_terminations.push(lockup_termination);
+            }
+        }
+
+        if total_unvested_balance > 0 {
+            let callback_gas = GAS_FOR_AFTER_FT_TRANSFER
+                + GAS_FOR_AFTER_LOCKUP_TERMINATION_PER_LOCKUP * lockup_terminations.len() as Gas;
+            let required_gas = GAS_FOR_FT_TRANSFER + callback_gas + GAS_RESERVED_FOR_TERMINATION;
+            assert!(
+                env::prepaid_gas() - env::used_gas() >= required_gas,
+                "Not enough gas to terminate {} lockups, {} TGas required",
+                lockup_terminations.len(),
+                (env::used_gas() + required_gas) / 10u64.pow(12),
+            );
+            ext_fungible_token::ft_transfer(
+                account_id.clone(),
+                total_unvested_balance.into(),
+                Some(format!("Terminated {} lockups", num_lockups)),
+                &self.token_account_id,
+                ONE_YOCTO,
+                GAS_FOR_FT_TRANSFER,
+            )
+            .then(ext_self::after_lockup_termination(
+                account_id,
+                lockup_terminations,
+                &env::current_account_id(),
+                NO_DEPOSIT,
+                callback_gas,
+            ))
+            .into()
+        } else {
+            PromiseOrValue::Value(0.into())
+        }
+    }
+
+    pub fn create_schedule_template(
+        &mut self,
+        template_id: TemplateId,
+        template: ScheduleTemplate,
+    ) {
+        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        template.assert_valid();
+        assert!(
+            self.schedule_templates
+                .insert(&template_id, &template)
+                .is_none(),
+            "schedule template already exists"
+        );
+    }
+
     #[payable]
     pub fn add_to_deposit_whitelist(&mut self, account_id: ValidAccountId) {
         assert_one_yocto();
@@ -271,14 +423,26 @@ impl Contract {
         self.deposit_whitelist.remove(account_id.as_ref());
     }
 
-    pub fn create_draft_group(&mut self) -> DraftGroupIndex {
+    /// Sets the maximum number of events kept per lockup. 0 disables the lockup history.
+    #[payable]
+    pub fn set_lockup_history_limit(&mut self, limit: u32) {
+        assert_one_yocto();
         self.assert_deposit_whitelist(&env::predecessor_account_id());
+        self.lockup_history_limit = limit;
+    }
+
+    pub fn create_draft_group(&mut self, metadata: Option<DraftMetadata>) -> DraftGroupIndex {
+        let account_id = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&account_id);
 
         let index = self.next_draft_group_id;
         self.next_draft_group_id += 1;
         assert!(
             self.draft_groups
-                .insert(&index, &DraftGroup::new())
+                .insert(
+                    &index,
+                    &DraftGroup::new(account_id.try_into().unwrap(), metadata)
+                )
                 .is_none(),
             "Invariant"
         );
@@ -286,46 +450,172 @@ impl Contract {
         index
     }
 
+    #[payable]
+    pub fn add_draft_group_operator(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        account_id: ValidAccountId,
+    ) {
+        assert_one_yocto();
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_creator(&env::predecessor_account_id());
+        draft_group.operators.insert(account_id.into());
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+    }
+
+    #[payable]
+    pub fn remove_draft_group_operator(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        account_id: ValidAccountId,
+    ) {
+        assert_one_yocto();
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_creator(&env::predecessor_account_id());
+        draft_group.operators.remove(account_id.as_ref());
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+    }
+
+    #[payable]
+    pub fn set_draft_group_conversion_policy(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        conversion_policy: DraftConversionPolicy,
+    ) {
+        assert_one_yocto();
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_creator(&env::predecessor_account_id());
+        draft_group.set_conversion_policy(conversion_policy);
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+    }
+
+    #[payable]
+    pub fn set_draft_group_acceptance(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        acceptance: Option<DraftAcceptance>,
+    ) {
+        assert_one_yocto();
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_creator(&env::predecessor_account_id());
+        draft_group.set_acceptance(acceptance);
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+    }
+
+    /// Enables the conversion of all drafts right after the draft group is funded.
+    /// Requires the `Anyone` conversion policy. Drafts are converted in batches, each batch
+    /// needs at least 100 TGas left after the previous one, so the funding `ft_transfer_call`
+    /// should attach enough gas for all batches. If the funding call runs out of gas,
+    /// the tokens are refunded. If a later batch does, its drafts can be converted manually.
+    #[payable]
+    pub fn set_draft_group_auto_convert(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        auto_convert: bool,
+    ) {
+        assert_one_yocto();
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_creator(&env::predecessor_account_id());
+        draft_group.set_auto_convert(auto_convert);
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+    }
+
+    /// Sets the creator and the termination beneficiary of the lockups created from the drafts.
+    /// It's required before the draft group can be funded by multiple payers.
+    #[payable]
+    pub fn set_draft_group_beneficiary(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        beneficiary_id: Option<ValidAccountId>,
+    ) {
+        assert_one_yocto();
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_creator(&env::predecessor_account_id());
+        draft_group.set_beneficiary(beneficiary_id);
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+    }
+
+    #[payable]
+    pub fn set_draft_group_funding_deadline(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        funding_deadline: Option<TimestampSec>,
+    ) {
+        assert_one_yocto();
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_creator(&env::predecessor_account_id());
+        draft_group.set_funding_deadline(funding_deadline);
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+    }
+
     pub fn create_draft(&mut self, draft: Draft) -> DraftIndex {
         self.create_drafts(vec![draft])[0]
     }
 
     pub fn create_drafts(&mut self, drafts: Vec<Draft>) -> Vec<DraftIndex> {
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
-        let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
-        let draft_ids: Vec<DraftIndex> = drafts
-            .iter()
-            .map(|draft| {
-                let draft_group = draft_group_lookup
-                    .entry(draft.draft_group_id)
-                    .or_insert_with(|| {
-                        self.draft_groups
-                            .get(&draft.draft_group_id as _)
-                            .expect("draft group not found")
-                    });
-                draft_group.assert_can_add_draft();
+        let account_id = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&account_id);
+        let drafts = drafts
+            .into_iter()
+            .map(|mut draft| {
                 draft.assert_new_valid();
-
-                let index = self.next_draft_id;
-                self.next_draft_id += 1;
-                assert!(self.drafts.insert(&index, &draft).is_none(), "Invariant");
-                draft_group.total_amount = draft_group
-                    .total_amount
-                    .checked_add(draft.total_balance())
-                    .expect("attempt to add with overflow");
-                draft_group.draft_indices.insert(index);
-
-                index
+                draft.created_at = current_timestamp_sec();
+                StoredDraft::Draft(draft)
             })
             .collect();
+        self.internal_create_drafts(&account_id, drafts)
+    }
 
-        draft_group_lookup
-            .iter()
-            .for_each(|(draft_group_id, draft_group)| {
-                self.draft_groups.insert(&draft_group_id as _, &draft_group);
-            });
-
-        draft_ids
+    /// Creates drafts that store only the template ID and the amount of their lockups.
+    pub fn create_drafts_from_template(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        lockups: Vec<TemplateLockupCreate>,
+    ) -> Vec<DraftIndex> {
+        let account_id = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&account_id);
+        let drafts = lockups
+            .into_iter()
+            .map(|template_lockup_create| {
+                let template =
+                    self.internal_get_schedule_template(&template_lockup_create.template_id);
+                Draft {
+                    draft_group_id,
+                    lockup_create: template_lockup_create.into_lockup_create(&template),
+                    metadata: None,
+                    created_at: 0,
+                }
+                .assert_new_valid();
+                StoredDraft::Template(TemplateDraft {
+                    draft_group_id,
+                    lockup_create: template_lockup_create,
+                    metadata: None,
+                    created_at: current_timestamp_sec(),
+                })
+            })
+            .collect();
+        self.internal_create_drafts(&account_id, drafts)
     }
 
     pub fn convert_draft(&mut self, draft_id: DraftIndex) -> LockupIndex {
@@ -333,39 +623,26 @@ impl Contract {
     }
 
     pub fn convert_drafts(&mut self, draft_ids: Vec<DraftIndex>) -> Vec<LockupIndex> {
+        let account_id = env::predecessor_account_id();
         let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
         let lockup_ids: Vec<LockupIndex> = draft_ids
             .iter()
             .map(|draft_id| {
-                let draft = self.drafts.remove(&draft_id as _).expect("draft not found");
+                let draft = self
+                    .internal_remove_draft(*draft_id)
+                    .expect("draft not found");
                 let draft_group = draft_group_lookup
-                    .entry(draft.draft_group_id)
+                    .entry(draft.draft_group_id())
                     .or_insert_with(|| {
                         self.draft_groups
-                            .get(&draft.draft_group_id as _)
+                            .get(&draft.draft_group_id() as _)
                             .expect("draft group not found")
                     });
                 draft_group.assert_can_convert_draft();
-                let payer_id = draft_group
-                    .payer_id
-                    .as_mut()
-                    .expect("expected present payer_id");
-
-                assert!(draft_group.draft_indices.remove(&draft_id), "Invariant");
-                let amount = draft.total_balance();
-                assert!(draft_group.total_amount >= amount, "Invariant");
-                draft_group.total_amount -= amount;
-
-                let lockup = draft.lockup_create.into_lockup(&payer_id);
-                let index = self.internal_add_lockup(&lockup);
-                log!(
-                    "Created new lockup for {} with index {} from draft {}",
-                    lockup.account_id.as_ref(),
-                    index,
-                    draft_id,
-                );
+                draft_group.assert_can_convert_draft_by(&draft, &account_id);
+                draft_group.assert_draft_accepted(*draft_id);
 
-                index
+                self.internal_convert_draft(*draft_id, draft, draft_group)
             })
             .collect();
 
@@ -382,14 +659,103 @@ impl Contract {
         lockup_ids
     }
 
+    pub fn accept_draft(&mut self, draft_id: DraftIndex, terms_hash: Option<Base58CryptoHash>) {
+        let draft = self.drafts.get(&draft_id as _).expect("draft not found");
+        assert_eq!(
+            draft.account_id().as_ref(),
+            &env::predecessor_account_id(),
+            "Only the draft recipient can accept the draft"
+        );
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft.draft_group_id() as _)
+            .expect("draft group not found");
+        draft_group.accept_draft(draft_id, terms_hash);
+        self.draft_groups
+            .insert(&draft.draft_group_id() as _, &draft_group);
+        log!("Accepted draft {}", draft_id);
+    }
+
+    #[payable]
+    pub fn cancel_draft_group(&mut self, draft_group_id: DraftGroupIndex) {
+        assert_one_yocto();
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_payer(&env::predecessor_account_id());
+        draft_group.cancel();
+        self.draft_groups.insert(&draft_group_id as _, &draft_group);
+        log!(
+            "Cancelled draft group {} with {} remaining",
+            draft_group_id,
+            draft_group.total_amount,
+        );
+    }
+
+    /// Returns the funds to the payers for unaccepted drafts after the acceptance has expired
+    /// or for any drafts of the cancelled draft group. The refund is split between the payers
+    /// pro rata to their contributions, the share of the caller is transferred right away.
+    pub fn reclaim_drafts(&mut self, draft_ids: Vec<DraftIndex>) -> PromiseOrValue<WrappedBalance> {
+        let account_id = env::predecessor_account_id();
+        let mut draft_group_lookup: HashMap<DraftGroupIndex, (DraftGroup, Balance)> =
+            HashMap::new();
+        for draft_id in &draft_ids {
+            let draft = self
+                .internal_remove_draft(*draft_id)
+                .expect("draft not found");
+            let (draft_group, refund_amount) = draft_group_lookup
+                .entry(draft.draft_group_id())
+                .or_insert_with(|| {
+                    let draft_group = self
+                        .draft_groups
+                        .get(&draft.draft_group_id() as _)
+                        .expect("draft group not found");
+                    (draft_group, 0)
+                });
+            draft_group.assert_can_reclaim_draft(*draft_id);
+            draft_group.assert_payer(&account_id);
+
+            let amount = draft.total_balance();
+            draft_group.remove_draft(*draft_id, amount);
+            *refund_amount += amount;
+            log!(
+                "Refunding {} from draft {} of draft group {}",
+                amount,
+                draft_id,
+                draft.draft_group_id(),
+            );
+        }
+
+        for (draft_group_id, (draft_group, refund_amount)) in &draft_group_lookup {
+            for (payer_id, share) in draft_group.refund_shares(*refund_amount, &account_id) {
+                self.internal_add_draft_refund(&payer_id, share);
+            }
+            if draft_group.draft_indices.is_empty() {
+                self.draft_groups.remove(draft_group_id);
+            } else {
+                self.draft_groups.insert(draft_group_id, draft_group);
+            }
+        }
+
+        self.internal_withdraw_draft_refunds(account_id)
+    }
+
+    /// Withdraws the refunds of reclaimed drafts and discarded draft groups.
+    pub fn withdraw_draft_refunds(&mut self) -> PromiseOrValue<WrappedBalance> {
+        self.internal_withdraw_draft_refunds(env::predecessor_account_id())
+    }
+
     pub fn discard_draft_group(&mut self, draft_group_id: DraftGroupIndex) {
-        self.assert_deposit_whitelist(&env::predecessor_account_id());
+        let account_id = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&account_id);
 
         let mut draft_group = self
             .draft_groups
             .get(&draft_group_id as _)
             .expect("draft group not found");
-        draft_group.discard();
+        draft_group.assert_operator(&account_id);
+        self.internal_discard_draft_group(draft_group_id, &mut draft_group);
 
         if draft_group.draft_indices.is_empty() {
             self.draft_groups.remove(&draft_group_id as _);
@@ -398,25 +764,70 @@ impl Contract {
         }
     }
 
+    /// Discards the draft group that has not been funded before the deadline and deletes up to
+    /// `limit` of its drafts. Can be called by anyone. Returns the number of remaining drafts.
+    pub fn cleanup_expired_draft_group(
+        &mut self,
+        draft_group_id: DraftGroupIndex,
+        limit: Option<u32>,
+    ) -> u32 {
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        assert!(draft_group.is_expired(), "draft group is not expired");
+        if !draft_group.discarded {
+            self.internal_discard_draft_group(draft_group_id, &mut draft_group);
+        }
+
+        let limit = limit.unwrap_or(DEFAULT_DRAFT_CLEANUP_LIMIT);
+        let draft_ids: Vec<DraftIndex> = draft_group
+            .draft_indices
+            .iter()
+            .take(limit as _)
+            .cloned()
+            .collect();
+        for draft_id in &draft_ids {
+            let draft = self.internal_remove_draft(*draft_id).expect("Invariant");
+            draft_group.remove_draft(*draft_id, draft.total_balance());
+        }
+
+        let remaining = draft_group.draft_indices.len() as u32;
+        log!(
+            "Deleted {} drafts of expired draft group {}, {} remaining",
+            draft_ids.len(),
+            draft_group_id,
+            remaining,
+        );
+        if remaining == 0 {
+            self.draft_groups.remove(&draft_group_id as _);
+        } else {
+            self.draft_groups.insert(&draft_group_id as _, &draft_group);
+        }
+
+        remaining
+    }
+
     pub fn delete_drafts(&mut self, draft_ids: Vec<DraftIndex>) {
-        // no authorization required here since the draft group discard has been authorized
+        let account_id = env::predecessor_account_id();
         let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
         for draft_id in &draft_ids {
-            let draft = self.drafts.remove(&draft_id as _).expect("draft not found");
+            let draft = self
+                .internal_remove_draft(*draft_id)
+                .expect("draft not found");
             let draft_group = draft_group_lookup
-                .entry(draft.draft_group_id)
+                .entry(draft.draft_group_id())
                 .or_insert_with(|| {
-                    self.draft_groups
-                        .get(&draft.draft_group_id as _)
-                        .expect("draft group not found")
+                    let draft_group = self
+                        .draft_groups
+                        .get(&draft.draft_group_id() as _)
+                        .expect("draft group not found");
+                    draft_group.assert_creator(&account_id);
+                    draft_group
                 });
 
             draft_group.assert_can_delete_draft();
-            let amount = draft.total_balance();
-            assert!(draft_group.total_amount >= amount, "Invariant");
-            draft_group.total_amount -= amount;
-
-            assert!(draft_group.draft_indices.remove(draft_id), "Invariant");
+            draft_group.remove_draft(*draft_id, draft.total_balance());
         }
 
         for (draft_group_id, draft_group) in &draft_group_lookup {
@@ -427,4 +838,183 @@ impl Contract {
             }
         }
     }
+
+    /// Replaces the lockup of the draft in the not funded draft group.
+    /// The previous acceptance of the draft is revoked.
+    pub fn update_draft(&mut self, draft_id: DraftIndex, lockup_create: LockupCreate) {
+        let account_id = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&account_id);
+        let mut draft = self.internal_get_draft(draft_id).expect("draft not found");
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft.draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_operator(&account_id);
+        draft_group.assert_can_update();
+
+        draft_group.remove_draft(draft_id, draft.total_balance());
+        draft.lockup_create = lockup_create;
+        draft.assert_new_valid();
+        draft_group.add_draft(draft_id, draft.total_balance());
+
+        self.draft_groups
+            .insert(&draft.draft_group_id as _, &draft_group);
+        self.internal_insert_draft(draft_id, &StoredDraft::Draft(draft));
+    }
+
+    /// Removes the draft from the not funded draft group.
+    pub fn remove_draft(&mut self, draft_id: DraftIndex) {
+        let account_id = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&account_id);
+        let draft = self.drafts.get(&draft_id as _).expect("draft not found");
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft.draft_group_id() as _)
+            .expect("draft group not found");
+        draft_group.assert_operator(&account_id);
+        draft_group.assert_can_update();
+
+        draft_group.remove_draft(draft_id, draft.total_balance());
+        self.internal_remove_draft(draft_id);
+        self.draft_groups
+            .insert(&draft.draft_group_id() as _, &draft_group);
+    }
+
+    /// Moves drafts between not funded draft groups. The acceptances of the drafts are revoked.
+    pub fn move_drafts(&mut self, draft_ids: Vec<DraftIndex>, draft_group_id: DraftGroupIndex) {
+        let account_id = env::predecessor_account_id();
+        self.assert_deposit_whitelist(&account_id);
+        let mut draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        draft_group.assert_operator(&account_id);
+        draft_group.assert_can_update();
+
+        let mut draft_group_lookup: HashMap<DraftGroupIndex, DraftGroup> = HashMap::new();
+        for draft_id in draft_ids {
+            let mut draft = self.drafts.get(&draft_id as _).expect("draft not found");
+            assert_ne!(
+                draft.draft_group_id(),
+                draft_group_id,
+                "draft is already in the draft group"
+            );
+            let source_draft_group = draft_group_lookup
+                .entry(draft.draft_group_id())
+                .or_insert_with(|| {
+                    let draft_group = self
+                        .draft_groups
+                        .get(&draft.draft_group_id() as _)
+                        .expect("draft group not found");
+                    draft_group.assert_operator(&account_id);
+                    draft_group.assert_can_update();
+                    draft_group
+                });
+
+            let amount = draft.total_balance();
+            source_draft_group.remove_draft(draft_id, amount);
+            draft_group.add_draft(draft_id, amount);
+            draft.set_draft_group_id(draft_group_id);
+            self.internal_insert_draft(draft_id, &draft);
+        }
+
+        draft_group_lookup.insert(draft_group_id, draft_group);
+        for (draft_group_id, draft_group) in &draft_group_lookup {
+            self.draft_groups.insert(draft_group_id, draft_group);
+        }
+    }
+
+    /// Compares the token balance of the contract with the sum of its obligations
+    /// and logs the discrepancy. The drift of the total value locked from the obligations
+    /// is logged as well. Returns whether the contract is solvent.
+    /// Iterates all lockups, draft groups and airdrops, so large states should be verified
+    /// page by page with `check_invariants`.
+    pub fn check_token_balance(&mut self) -> Promise {
+        let obligations = self.internal_total_obligations();
+        ext_fungible_token::ft_balance_of(
+            env::current_account_id(),
+            &self.token_account_id,
+            NO_DEPOSIT,
+            GAS_FOR_FT_BALANCE_OF,
+        )
+        .then(ext_self::after_token_balance_check(
+            obligations.into(),
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            GAS_FOR_AFTER_TOKEN_BALANCE_CHECK,
+        ))
+    }
+
+    pub fn create_airdrop(&mut self, airdrop: AirdropCreate) -> AirdropIndex {
+        self.assert_deposit_whitelist(&env::predecessor_account_id());
+
+        let index = self.airdrops.len() as AirdropIndex;
+        self.airdrops.push(&Airdrop::new(airdrop));
+
+        index
+    }
+
+    pub fn claim_airdrop(
+        &mut self,
+        airdrop_id: AirdropIndex,
+        amount: WrappedBalance,
+        proof: Vec<Base58CryptoHash>,
+    ) -> LockupIndex {
+        let account_id: ValidAccountId = env::predecessor_account_id().try_into().unwrap();
+        let mut airdrop = self
+            .airdrops
+            .get(airdrop_id as _)
+            .expect("airdrop not found");
+        assert!(
+            self.airdrop_claims
+                .insert(&(airdrop_id, account_id.as_ref().clone())),
+            "airdrop already claimed"
+        );
+
+        let lockup = airdrop.claim(&account_id, amount.0, &proof);
+        self.airdrops.replace(airdrop_id as _, &airdrop);
+        let index = self.internal_add_lockup(&lockup);
+        log!(
+            "Created new lockup for {} with index {} from airdrop {}",
+            lockup.account_id.as_ref(),
+            index,
+            airdrop_id,
+        );
+
+        index
+    }
+
+    /// Returns the unclaimed balance of the airdrop to the payer after the claim deadline.
+    #[payable]
+    pub fn reclaim_airdrop(&mut self, airdrop_id: AirdropIndex) -> PromiseOrValue<WrappedBalance> {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut airdrop = self
+            .airdrops
+            .get(airdrop_id as _)
+            .expect("airdrop not found");
+        let amount = airdrop.reclaim(&account_id);
+        self.airdrops.replace(airdrop_id as _, &airdrop);
+        if amount == 0 {
+            return PromiseOrValue::Value(0.into());
+        }
+        log!("Reclaiming {} from airdrop {}", amount, airdrop_id);
+
+        ext_fungible_token::ft_transfer(
+            account_id,
+            amount.into(),
+            Some(format!("Reclaimed airdrop #{}", airdrop_id)),
+            &self.token_account_id,
+            ONE_YOCTO,
+            GAS_FOR_FT_TRANSFER,
+        )
+        .then(ext_self::after_airdrop_reclaim(
+            airdrop_id,
+            amount.into(),
+            &env::current_account_id(),
+            NO_DEPOSIT,
+            GAS_FOR_AFTER_FT_TRANSFER,
+        ))
+        .into()
+    }
 }
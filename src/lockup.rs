@@ -1,5 +1,4 @@
 use crate::*;
-use std::convert::TryInto;
 
 pub type LockupIndex = u32;
 
@@ -18,21 +17,133 @@ pub struct LockupClaim {
 pub struct Lockup {
     pub account_id: ValidAccountId,
     pub schedule: Schedule,
+    /// The account that funded the lockup.
+    pub creator_id: ValidAccountId,
 
     #[serde(default)]
     #[serde(with = "u128_dec_format")]
     pub claimed_balance: Balance,
     /// An optional configuration that allows vesting/lockup termination.
     pub termination_config: Option<TerminationConfig>,
+    /// The schedule template of the lockup. Such lockups don't store their schedules,
+    /// they are scaled from the template. It's cleared once the lockup is terminated.
+    #[serde(default)]
+    pub template_id: Option<TemplateId>,
+}
+
+/// The stored form of a lockup.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum StoredLockup {
+    Lockup(Lockup),
+    Template(TemplateLockup),
+}
+
+impl From<&Lockup> for StoredLockup {
+    fn from(lockup: &Lockup) -> Self {
+        match &lockup.template_id {
+            None => Self::Lockup(Lockup {
+                account_id: lockup.account_id.clone(),
+                schedule: lockup.schedule.clone(),
+                creator_id: lockup.creator_id.clone(),
+                claimed_balance: lockup.claimed_balance,
+                termination_config: lockup.termination_config.clone(),
+                template_id: None,
+            }),
+            Some(template_id) => Self::Template(TemplateLockup {
+                account_id: lockup.account_id.clone(),
+                creator_id: lockup.creator_id.clone(),
+                template_id: template_id.clone(),
+                total_balance: lockup.schedule.total_balance(),
+                claimed_balance: lockup.claimed_balance,
+                beneficiary_id: lockup
+                    .termination_config
+                    .as_ref()
+                    .map(|termination_config| termination_config.beneficiary_id.clone()),
+            }),
+        }
+    }
+}
+
+/// Lockups matching all the given fields are returned by `find_lockups`.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+pub struct LockupFilter {
+    pub account_id: Option<ValidAccountId>,
+    pub creator_id: Option<ValidAccountId>,
+    /// Whether the lockup has a termination config.
+    pub terminable: Option<bool>,
+    pub fully_claimed: Option<bool>,
+    pub unlock_start_from: Option<TimestampSec>,
+    pub unlock_start_to: Option<TimestampSec>,
+    pub min_total_balance: Option<WrappedBalance>,
+}
+
+/// The key to sort the lockups returned by `find_lockups` by.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub enum LockupSortKey {
+    /// The lockup index, i.e. the order the lockups were created in.
+    #[default]
+    Index,
+    /// The timestamp of the last checkpoint with zero balance.
+    UnlockStart,
+    /// The total balance of the lockup schedule.
+    TotalBalance,
+}
+
+/// The order of the lockups returned by `find_lockups`. Lockups with equal keys are ordered
+/// by the lockup index in the same direction.
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub struct LockupSort {
+    #[serde(default)]
+    pub key: LockupSortKey,
+    #[serde(default)]
+    pub descending: bool,
 }
 
 impl Lockup {
-    pub fn new_unlocked(account_id: AccountId, total_balance: Balance) -> Self {
-        Self {
-            account_id: account_id.try_into().unwrap(),
-            schedule: Schedule::new_unlocked(total_balance),
-            claimed_balance: 0,
-            termination_config: None,
+    pub fn matches(&self, filter: &LockupFilter) -> bool {
+        let total_balance = self.schedule.total_balance();
+        let unlock_start = self.schedule.unlock_start_timestamp();
+        filter
+            .account_id
+            .iter()
+            .all(|account_id| account_id == &self.account_id)
+            && filter
+                .creator_id
+                .iter()
+                .all(|creator_id| creator_id == &self.creator_id)
+            && filter
+                .terminable
+                .iter()
+                .all(|terminable| self.termination_config.is_some() == *terminable)
+            && filter
+                .fully_claimed
+                .iter()
+                .all(|fully_claimed| (self.claimed_balance == total_balance) == *fully_claimed)
+            && filter
+                .unlock_start_from
+                .iter()
+                .all(|unlock_start_from| unlock_start >= *unlock_start_from)
+            && filter
+                .unlock_start_to
+                .iter()
+                .all(|unlock_start_to| unlock_start <= *unlock_start_to)
+            && filter
+                .min_total_balance
+                .iter()
+                .all(|min_total_balance| total_balance >= min_total_balance.0)
+    }
+
+    pub fn sort_value(&self, key: LockupSortKey) -> Balance {
+        match key {
+            LockupSortKey::Index => 0,
+            LockupSortKey::UnlockStart => self.schedule.unlock_start_timestamp() as Balance,
+            LockupSortKey::TotalBalance => self.schedule.total_balance(),
         }
     }
 
@@ -89,6 +200,27 @@ pub struct LockupCreate {
     pub vesting_schedule: Option<VestingConditions>,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+pub struct FractionalLockupCreate {
+    pub account_id: ValidAccountId,
+    pub schedule: FractionalSchedule,
+    pub vesting_schedule: Option<FractionalSchedule>,
+}
+
+impl FractionalLockupCreate {
+    pub fn into_lockup_create(&self, total_balance: Balance) -> LockupCreate {
+        LockupCreate {
+            account_id: self.account_id.clone(),
+            schedule: self.schedule.into_schedule(total_balance),
+            vesting_schedule: self.vesting_schedule.as_ref().map(|vesting_schedule| {
+                VestingConditions::Schedule(vesting_schedule.into_schedule(total_balance))
+            }),
+        }
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 impl LockupCreate {
     pub fn new_unlocked(account_id: ValidAccountId, total_balance: Balance) -> Self {
@@ -106,6 +238,7 @@ impl LockupCreate {
         Lockup {
             account_id: self.account_id.clone(),
             schedule: self.schedule.clone(),
+            creator_id: payer_id.clone(),
             claimed_balance: 0,
             termination_config: match vesting_schedule {
                 None => None,
@@ -114,6 +247,7 @@ impl LockupCreate {
                     vesting_schedule,
                 }),
             },
+            template_id: None,
         }
     }
 }
@@ -0,0 +1,177 @@
+use crate::*;
+
+/// The lockup before it stored its creator and template.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldLockup {
+    pub account_id: ValidAccountId,
+    pub schedule: Schedule,
+    pub claimed_balance: Balance,
+    pub termination_config: Option<TerminationConfig>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldDraft {
+    pub draft_group_id: DraftGroupIndex,
+    pub lockup_create: LockupCreate,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldDraftGroup {
+    pub total_amount: Balance,
+    pub payer_id: Option<ValidAccountId>,
+    pub draft_indices: HashSet<DraftIndex>,
+    pub discarded: bool,
+}
+
+/// The contract state of the initial release.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldContract {
+    pub token_account_id: TokenAccountId,
+    pub lockups: Vector<OldLockup>,
+    pub account_lockups: LookupMap<AccountId, HashSet<LockupIndex>>,
+    pub deposit_whitelist: UnorderedSet<AccountId>,
+    pub next_draft_id: DraftIndex,
+    pub drafts: LookupMap<DraftIndex, OldDraft>,
+    pub next_draft_group_id: DraftGroupIndex,
+    pub draft_groups: UnorderedMap<DraftGroupIndex, OldDraftGroup>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Migrates the state of the initial release in a single call.
+    ///
+    /// The old lockups don't know their funder, so the creator of the terminable lockups is
+    /// the termination beneficiary and the `legacy_creator_id` otherwise. The old draft groups
+    /// are created by the `legacy_creator_id`. The lockups that were removed from the account
+    /// lockups once fully claimed are archived. The stats are rebuilt from the migrated lockups
+    /// and draft groups, so the earlier terminations are not counted.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(legacy_creator_id: ValidAccountId) -> Self {
+        let OldContract {
+            token_account_id,
+            lockups: old_lockups,
+            account_lockups,
+            deposit_whitelist,
+            next_draft_id,
+            drafts: mut old_drafts,
+            next_draft_group_id,
+            draft_groups: mut old_draft_groups,
+        } = env::state_read().expect("Old state doesn't exist");
+        let mut contract = Self {
+            token_account_id,
+            lockups: Vector::new(StorageKey::Lockups),
+            account_lockups,
+            account_archived_lockups: LookupMap::new(StorageKey::AccountArchivedLockups),
+            lockup_history: LookupMap::new(StorageKey::LockupHistory),
+            lockup_history_limit: DEFAULT_LOCKUP_HISTORY_LIMIT,
+            creator_lockups: LookupMap::new(StorageKey::CreatorLockups),
+            beneficiary_lockups: LookupMap::new(StorageKey::BeneficiaryLockups),
+            deposit_whitelist,
+            next_draft_id,
+            drafts: LookupMap::new(StorageKey::Drafts),
+            next_draft_group_id,
+            draft_groups: UnorderedMap::new(StorageKey::DraftGroups),
+            account_drafts: LookupMap::new(StorageKey::AccountDrafts),
+            draft_refunds: LookupMap::new(StorageKey::DraftRefunds),
+            airdrops: Vector::new(StorageKey::Airdrops),
+            airdrop_claims: LookupSet::new(StorageKey::AirdropClaims),
+            schedule_templates: UnorderedMap::new(StorageKey::ScheduleTemplates),
+            stats: Stats::default(),
+        };
+
+        // the new collections reuse the old prefixes, so the old lockups are read before
+        // they are overwritten by the new ones at the same positions
+        let mut account_lockups: HashMap<AccountId, HashSet<LockupIndex>> = HashMap::new();
+        for (index, old_lockup) in old_lockups.to_vec().into_iter().enumerate() {
+            let index = index as LockupIndex;
+            let creator_id = old_lockup
+                .termination_config
+                .as_ref()
+                .map_or(&legacy_creator_id, |termination_config| {
+                    &termination_config.beneficiary_id
+                })
+                .clone();
+            let lockup = Lockup {
+                account_id: old_lockup.account_id,
+                schedule: old_lockup.schedule,
+                creator_id,
+                claimed_balance: old_lockup.claimed_balance,
+                termination_config: old_lockup.termination_config,
+                template_id: None,
+            };
+            contract.lockups.push(&(&lockup).into());
+            contract.stats.total_deposited += lockup.schedule.total_balance();
+            contract.stats.total_claimed += lockup.claimed_balance;
+
+            let creator_id = lockup.creator_id.as_ref();
+            let mut indices = contract.creator_lockups.get(creator_id).unwrap_or_else(|| {
+                Vector::new(StorageKey::CreatorLockupsByAccount {
+                    account_hash: hash_account_id(creator_id),
+                })
+            });
+            indices.push(&index);
+            contract.creator_lockups.insert(creator_id, &indices);
+
+            if let Some(termination_config) = &lockup.termination_config {
+                contract.internal_add_beneficiary_lockup(
+                    termination_config.beneficiary_id.as_ref(),
+                    index,
+                );
+            }
+
+            let account_id = lockup.account_id.as_ref();
+            let indices = account_lockups
+                .entry(account_id.clone())
+                .or_insert_with(|| contract.account_lockups.get(account_id).unwrap_or_default());
+            if !indices.contains(&index) {
+                let mut archive = contract
+                    .account_archived_lockups
+                    .get(account_id)
+                    .unwrap_or_else(|| {
+                        UnorderedSet::new(StorageKey::AccountArchivedLockupsByAccount {
+                            account_hash: hash_account_id(account_id),
+                        })
+                    });
+                archive.insert(&index);
+                contract
+                    .account_archived_lockups
+                    .insert(account_id, &archive);
+            }
+        }
+        for indices in account_lockups.values() {
+            contract.stats.on_account_lockups_update(0, indices.len());
+        }
+
+        // the old draft groups are cleared, since their keys would be reused with old values
+        let draft_groups = old_draft_groups.to_vec();
+        old_draft_groups.clear();
+        for (draft_group_id, old_draft_group) in draft_groups {
+            let mut draft_group = DraftGroup::new(legacy_creator_id.clone(), None);
+            draft_group.total_amount = old_draft_group.total_amount;
+            draft_group.discarded = old_draft_group.discarded;
+            if let Some(payer_id) = old_draft_group.payer_id {
+                draft_group
+                    .contributions
+                    .insert(payer_id.as_ref().clone(), old_draft_group.total_amount);
+                draft_group.funded_amount = old_draft_group.total_amount;
+                draft_group.payer_id = Some(payer_id);
+                contract.stats.total_deposited += old_draft_group.total_amount;
+            }
+            for draft_id in old_draft_group.draft_indices {
+                let old_draft = old_drafts.remove(&draft_id).expect("draft not found");
+                let draft = Draft {
+                    draft_group_id: old_draft.draft_group_id,
+                    lockup_create: old_draft.lockup_create,
+                    metadata: None,
+                    created_at: 0,
+                };
+                contract.internal_insert_draft(draft_id, &StoredDraft::Draft(draft));
+                draft_group.draft_indices.insert(draft_id);
+            }
+            contract.draft_groups.insert(&draft_group_id, &draft_group);
+        }
+
+        contract
+    }
+}
@@ -15,6 +15,49 @@ pub struct Checkpoint {
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
 pub struct Schedule(pub Vec<Checkpoint>);
 
+/// The denominator of the fractional checkpoint balances, 100%.
+pub const BASIS_POINTS: u32 = 10_000;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub struct FractionalCheckpoint {
+    /// The unix-timestamp in seconds since the epoch.
+    pub timestamp: TimestampSec,
+    /// The unlocked fraction of the total balance in basis points.
+    pub basis_points: u32,
+}
+
+/// A schedule with the checkpoint balances expressed as fractions of the total balance.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub struct FractionalSchedule(pub Vec<FractionalCheckpoint>);
+
+impl FractionalSchedule {
+    /// Computes the schedule for the given total balance.
+    /// Intermediate balances are rounded down, the last checkpoint balance is the total balance.
+    pub fn into_schedule(&self, total_balance: Balance) -> Schedule {
+        assert_eq!(
+            self.0.last().map(|checkpoint| checkpoint.basis_points),
+            Some(BASIS_POINTS),
+            "The last checkpoint should unlock {} basis points",
+            BASIS_POINTS
+        );
+        let schedule = Schedule(
+            self.0
+                .iter()
+                .map(|checkpoint| Checkpoint {
+                    timestamp: checkpoint.timestamp,
+                    balance: checkpoint.basis_points as _,
+                })
+                .collect(),
+        );
+        schedule.assert_valid(BASIS_POINTS as _);
+        schedule.scale(total_balance)
+    }
+}
+
 impl Schedule {
     pub fn new_unlocked(total_balance: Balance) -> Self {
         Self(vec![
@@ -103,6 +146,28 @@ impl Schedule {
             .as_u128()
     }
 
+    /// Returns the timestamp of the next checkpoint that unlocks more than the balance unlocked
+    /// at the given timestamp.
+    pub fn next_unlock_timestamp(&self, timestamp: TimestampSec) -> Option<TimestampSec> {
+        let unlocked_balance = self.unlocked_balance(timestamp);
+        self.0
+            .iter()
+            .find(|checkpoint| {
+                checkpoint.timestamp > timestamp && checkpoint.balance > unlocked_balance
+            })
+            .map(|checkpoint| checkpoint.timestamp)
+    }
+
+    /// Returns the timestamp of the last checkpoint before any balance is unlocked.
+    pub fn unlock_start_timestamp(&self) -> TimestampSec {
+        self.0
+            .iter()
+            .take_while(|checkpoint| checkpoint.balance == 0)
+            .last()
+            .unwrap_or(&self.0[0])
+            .timestamp
+    }
+
     pub fn total_balance(&self) -> Balance {
         self.0.last().unwrap().balance
     }
@@ -140,6 +205,23 @@ impl Schedule {
         unreachable!();
     }
 
+    /// Scales the schedule to the new total balance, rounding intermediate balances down.
+    /// The last checkpoint balance is always equal to the new total balance.
+    pub fn scale(&self, new_total_balance: Balance) -> Self {
+        let total_balance = self.total_balance();
+        Self(
+            self.0
+                .iter()
+                .map(|checkpoint| Checkpoint {
+                    timestamp: checkpoint.timestamp,
+                    balance: (U256::from(checkpoint.balance) * U256::from(new_total_balance)
+                        / U256::from(total_balance))
+                    .as_u128(),
+                })
+                .collect(),
+        )
+    }
+
     pub fn hash(&self) -> CryptoHash {
         let value_hash = env::sha256(&self.try_to_vec().unwrap());
         let mut res = CryptoHash::default();
@@ -0,0 +1,42 @@
+use crate::*;
+
+/// Running totals of the contract updated on every balance change.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct Stats {
+    /// The total amount of tokens transferred to the contract.
+    pub total_deposited: Balance,
+    /// The total amount of tokens claimed from lockups.
+    pub total_claimed: Balance,
+    /// The total amount of unvested tokens refunded by lockup terminations.
+    pub total_terminated: Balance,
+    /// The total amount of tokens refunded from draft groups.
+    pub total_draft_refunded: Balance,
+    /// The total amount of unclaimed airdrop balances returned to the payers.
+    pub total_airdrop_reclaimed: Balance,
+    /// The total amount of draft group refunds that are not yet withdrawn.
+    pub pending_draft_refunds: Balance,
+    /// The number of lockups that are not yet fully claimed.
+    pub active_lockups: u64,
+    /// The number of accounts with at least one active lockup.
+    pub active_accounts: u64,
+}
+
+impl Stats {
+    /// Returns the amount of tokens that should be held by the contract.
+    pub fn total_value_locked(&self) -> Balance {
+        self.total_deposited
+            - self.total_claimed
+            - self.total_terminated
+            - self.total_draft_refunded
+            - self.total_airdrop_reclaimed
+    }
+
+    pub fn on_account_lockups_update(&mut self, old_len: usize, new_len: usize) {
+        self.active_lockups = self.active_lockups + new_len as u64 - old_len as u64;
+        if old_len == 0 && new_len > 0 {
+            self.active_accounts += 1;
+        } else if old_len > 0 && new_len == 0 {
+            self.active_accounts -= 1;
+        }
+    }
+}
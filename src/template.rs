@@ -0,0 +1,125 @@
+use crate::*;
+
+pub type TemplateId = String;
+
+/// A schedule shared by many lockups. The checkpoint balances are treated as fractions of the
+/// schedule total balance, so the template can be scaled to any lockup amount.
+/// Templates can't be changed or removed, because the lockups and the drafts created from them
+/// only store the template ID. All whitelisted accounts share one namespace of template IDs.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+pub struct ScheduleTemplate {
+    pub schedule: Schedule,
+    pub vesting_schedule: Option<VestingConditions>,
+}
+
+impl ScheduleTemplate {
+    pub fn assert_valid(&self) {
+        let total_balance = self.schedule.total_balance();
+        self.schedule.assert_valid(total_balance);
+        match &self.vesting_schedule {
+            None | Some(VestingConditions::SameAsLockupSchedule) => {}
+            Some(VestingConditions::Hash(_hash)) => {
+                env::panic(b"The vesting schedule hash cannot be used in a template");
+            }
+            Some(VestingConditions::Schedule(schedule)) => {
+                schedule.assert_valid(total_balance);
+                self.schedule.assert_valid_termination_schedule(schedule);
+            }
+        }
+    }
+
+    /// Returns the lockup schedule and the vesting conditions scaled to the given amount.
+    pub fn scale(&self, amount: Balance) -> (Schedule, Option<VestingConditions>) {
+        let vesting_schedule =
+            self.vesting_schedule
+                .as_ref()
+                .map(|vesting_schedule| match vesting_schedule {
+                    VestingConditions::Schedule(schedule) => {
+                        VestingConditions::Schedule(schedule.scale(amount))
+                    }
+                    vesting_schedule => vesting_schedule.clone(),
+                });
+        (self.schedule.scale(amount), vesting_schedule)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+pub struct TemplateLockupCreate {
+    pub account_id: ValidAccountId,
+    pub template_id: TemplateId,
+    pub amount: WrappedBalance,
+}
+
+impl TemplateLockupCreate {
+    pub fn into_lockup_create(&self, template: &ScheduleTemplate) -> LockupCreate {
+        let (schedule, vesting_schedule) = template.scale(self.amount.0);
+        LockupCreate {
+            account_id: self.account_id.clone(),
+            schedule,
+            vesting_schedule,
+        }
+    }
+
+    pub fn into_lockup(&self, template: &ScheduleTemplate, payer_id: &ValidAccountId) -> Lockup {
+        let mut lockup = self.into_lockup_create(template).into_lockup(payer_id);
+        lockup.template_id = Some(self.template_id.clone());
+        lockup
+    }
+}
+
+/// A lockup created from a template. Only the template ID and the total balance are stored,
+/// the schedule and the vesting conditions are scaled from the template when it's loaded.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TemplateLockup {
+    pub account_id: ValidAccountId,
+    pub creator_id: ValidAccountId,
+    pub template_id: TemplateId,
+    pub total_balance: Balance,
+    pub claimed_balance: Balance,
+    /// The termination beneficiary, present if the template has the vesting schedule.
+    pub beneficiary_id: Option<ValidAccountId>,
+}
+
+impl TemplateLockup {
+    pub fn into_lockup(self, template: &ScheduleTemplate) -> Lockup {
+        let (schedule, vesting_schedule) = template.scale(self.total_balance);
+        Lockup {
+            account_id: self.account_id,
+            schedule,
+            creator_id: self.creator_id,
+            claimed_balance: self.claimed_balance,
+            termination_config: vesting_schedule.zip(self.beneficiary_id).map(
+                |(vesting_schedule, beneficiary_id)| TerminationConfig {
+                    beneficiary_id,
+                    vesting_schedule,
+                },
+            ),
+            template_id: Some(self.template_id),
+        }
+    }
+}
+
+/// A draft created from a template. Like `TemplateLockup` it only stores the template ID
+/// and the amount.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TemplateDraft {
+    pub draft_group_id: DraftGroupIndex,
+    pub lockup_create: TemplateLockupCreate,
+    pub metadata: Option<DraftMetadata>,
+    pub created_at: TimestampSec,
+}
+
+impl TemplateDraft {
+    pub fn into_draft(self, template: &ScheduleTemplate) -> Draft {
+        Draft {
+            draft_group_id: self.draft_group_id,
+            lockup_create: self.lockup_create.into_lockup_create(template),
+            metadata: self.metadata,
+            created_at: self.created_at,
+        }
+    }
+}
@@ -9,9 +9,9 @@ pub enum VestingConditions {
     Schedule(Schedule),
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
-#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Clone))]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
 pub struct TerminationConfig {
     /// The account ID who paid for the lockup creation
     /// and will receive unvested balance upon termination
@@ -20,7 +20,38 @@ pub struct TerminationConfig {
     pub vesting_schedule: VestingConditions,
 }
 
+/// The state of a lockup before its termination.
+/// Used to restore the lockup in case the unvested balance transfer fails.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub struct LockupTermination {
+    pub index: LockupIndex,
+    pub schedule: Schedule,
+    pub termination_config: TerminationConfig,
+    pub template_id: Option<TemplateId>,
+    pub unvested_balance: WrappedBalance,
+}
+
 impl Lockup {
+    /// Returns the vested balance at the given timestamp or `None` if the vesting schedule
+    /// is hidden behind the hash. Lockups without termination config are fully vested.
+    pub fn vested_balance(&self, timestamp: TimestampSec) -> Option<Balance> {
+        match &self.termination_config {
+            None => Some(self.schedule.total_balance()),
+            Some(termination_config) => match &termination_config.vesting_schedule {
+                VestingConditions::SameAsLockupSchedule => {
+                    Some(self.schedule.unlocked_balance(timestamp))
+                }
+                VestingConditions::Hash(_hash) => None,
+                VestingConditions::Schedule(schedule) => Some(std::cmp::min(
+                    schedule.unlocked_balance(timestamp),
+                    self.schedule.total_balance(),
+                )),
+            },
+        }
+    }
+
     pub fn terminate(
         &mut self,
         initiator_id: &AccountId,
@@ -36,6 +67,8 @@ impl Lockup {
             initiator_id,
             "Unauthorized"
         );
+        // the schedule is no longer the scaled template
+        self.template_id = None;
         let total_balance = self.schedule.total_balance();
         let vested_balance = match &termination_config.vesting_schedule {
             VestingConditions::SameAsLockupSchedule => &self.schedule,
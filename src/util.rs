@@ -8,6 +8,13 @@ pub(crate) fn current_timestamp_sec() -> TimestampSec {
     nano_to_sec(env::block_timestamp())
 }
 
+/// Returns the hash of the account ID to build the storage prefix of its collection.
+pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
+    let mut res = CryptoHash::default();
+    res.copy_from_slice(&env::sha256(account_id.as_bytes()));
+    res
+}
+
 pub mod u128_dec_format {
     use near_sdk::serde::de;
     use near_sdk::serde::{Deserialize, Deserializer, Serializer};
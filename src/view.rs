@@ -7,17 +7,33 @@ use std::convert::TryInto;
 pub struct LockupView {
     pub account_id: ValidAccountId,
     pub schedule: Schedule,
+    pub creator_id: ValidAccountId,
 
     #[serde(default)]
     #[serde(with = "u128_dec_format")]
     pub claimed_balance: Balance,
     /// An optional configuration that allows vesting/lockup termination.
     pub termination_config: Option<TerminationConfig>,
+    /// The schedule template the lockup was created from.
+    pub template_id: Option<TemplateId>,
 
     #[serde(with = "u128_dec_format")]
     pub total_balance: Balance,
     #[serde(with = "u128_dec_format")]
     pub unclaimed_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub unlocked_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub locked_balance: Balance,
+    /// The unlocked fraction of the total balance in basis points.
+    pub unlocked_basis_points: u32,
+    /// The first checkpoint after the current timestamp or `None` if the schedule is finished.
+    pub next_checkpoint: Option<Checkpoint>,
+    pub start_timestamp: TimestampSec,
+    pub end_timestamp: TimestampSec,
+    pub terminable: bool,
+    /// The vested balance or `None` if the vesting schedule is hidden behind the hash.
+    pub vested_balance: Option<WrappedBalance>,
     /// The current timestamp
     pub timestamp: TimestampSec,
 }
@@ -26,26 +42,66 @@ impl From<Lockup> for LockupView {
     fn from(lockup: Lockup) -> Self {
         let total_balance = lockup.schedule.total_balance();
         let timestamp = current_timestamp_sec();
-        let unclaimed_balance =
-            lockup.schedule.unlocked_balance(timestamp) - lockup.claimed_balance;
+        let unlocked_balance = lockup.schedule.unlocked_balance(timestamp);
+        let unclaimed_balance = unlocked_balance - lockup.claimed_balance;
+        let unlocked_basis_points = if total_balance == 0 {
+            BASIS_POINTS
+        } else {
+            (U256::from(unlocked_balance) * U256::from(BASIS_POINTS) / U256::from(total_balance))
+                .as_u32()
+        };
+        let next_checkpoint = lockup
+            .schedule
+            .0
+            .iter()
+            .find(|checkpoint| checkpoint.timestamp > timestamp)
+            .cloned();
+        let start_timestamp = lockup.schedule.0.first().unwrap().timestamp;
+        let end_timestamp = lockup.schedule.0.last().unwrap().timestamp;
+        let terminable = lockup.termination_config.is_some();
+        let vested_balance = lockup
+            .vested_balance(timestamp)
+            .map(|vested_balance| vested_balance.into());
         let Lockup {
             account_id,
             schedule,
+            creator_id,
             claimed_balance,
             termination_config,
+            template_id,
         } = lockup;
         Self {
             account_id,
             schedule,
+            creator_id,
             claimed_balance,
             termination_config,
+            template_id,
             total_balance,
             unclaimed_balance,
+            unlocked_balance,
+            locked_balance: total_balance - unlocked_balance,
+            unlocked_basis_points,
+            next_checkpoint,
+            start_timestamp,
+            end_timestamp,
+            terminable,
+            vested_balance,
             timestamp,
         }
     }
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct LockupSearchView {
+    pub lockups: Vec<(LockupIndex, LockupView)>,
+    /// The lockup index to continue the search from or `None` if all lockups were scanned.
+    /// It's passed as `from_index` with the same filter and sort to get the next page.
+    pub next_index: Option<LockupIndex>,
+}
+
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
@@ -93,19 +149,53 @@ pub struct DraftGroupView {
     #[serde(with = "u128_dec_format")]
     pub total_amount: Balance,
     pub payer_id: Option<ValidAccountId>,
+    pub beneficiary_id: Option<ValidAccountId>,
+    pub contributions: Vec<(AccountId, WrappedBalance)>,
+    #[serde(with = "u128_dec_format")]
+    pub funded_amount: Balance,
     pub draft_indices: Vec<DraftIndex>,
     pub discarded: bool,
     pub funded: bool,
+    pub creator_id: ValidAccountId,
+    pub operators: Vec<AccountId>,
+    pub conversion_policy: DraftConversionPolicy,
+    pub acceptance: Option<DraftAcceptance>,
+    pub accepted_draft_indices: Vec<DraftIndex>,
+    pub cancellable_after: Option<TimestampSec>,
+    pub metadata: Option<DraftMetadata>,
+    pub created_at: TimestampSec,
+    pub funding_deadline: Option<TimestampSec>,
+    pub expired: bool,
+    pub auto_convert: bool,
 }
 
 impl From<DraftGroup> for DraftGroupView {
     fn from(draft_group: DraftGroup) -> Self {
+        let expired = draft_group.is_expired();
         Self {
             total_amount: draft_group.total_amount,
             payer_id: draft_group.payer_id.clone(),
+            beneficiary_id: draft_group.beneficiary_id.clone(),
+            contributions: draft_group
+                .contributions
+                .into_iter()
+                .map(|(payer_id, contribution)| (payer_id, contribution.into()))
+                .collect(),
+            funded_amount: draft_group.funded_amount,
             draft_indices: draft_group.draft_indices.into_iter().collect(),
             discarded: draft_group.discarded,
             funded: draft_group.payer_id.is_some(),
+            creator_id: draft_group.creator_id,
+            operators: draft_group.operators.into_iter().collect(),
+            conversion_policy: draft_group.conversion_policy,
+            acceptance: draft_group.acceptance,
+            accepted_draft_indices: draft_group.accepted_draft_indices.into_iter().collect(),
+            cancellable_after: draft_group.cancellable_after,
+            metadata: draft_group.metadata,
+            created_at: draft_group.created_at,
+            funding_deadline: draft_group.funding_deadline,
+            expired,
+            auto_convert: draft_group.auto_convert,
         }
     }
 }
@@ -116,6 +206,8 @@ impl From<DraftGroup> for DraftGroupView {
 pub struct DraftView {
     pub draft_group_id: DraftGroupIndex,
     pub lockup_create: LockupCreateView,
+    pub metadata: Option<DraftMetadata>,
+    pub created_at: TimestampSec,
 }
 
 impl From<Draft> for DraftView {
@@ -123,10 +215,169 @@ impl From<Draft> for DraftView {
         Self {
             draft_group_id: draft.draft_group_id,
             lockup_create: draft.lockup_create.into(),
+            metadata: draft.metadata,
+            created_at: draft.created_at,
         }
     }
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct AirdropView {
+    pub merkle_root: Base58CryptoHash,
+    pub schedule: Schedule,
+    #[serde(with = "u128_dec_format")]
+    pub total_amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub claimed_amount: Balance,
+    pub payer_id: Option<ValidAccountId>,
+    pub funded: bool,
+    pub claim_deadline: TimestampSec,
+    pub reclaimed: bool,
+}
+
+impl From<Airdrop> for AirdropView {
+    fn from(airdrop: Airdrop) -> Self {
+        Self {
+            merkle_root: airdrop.merkle_root.into(),
+            schedule: airdrop.schedule,
+            total_amount: airdrop.total_amount,
+            claimed_amount: airdrop.claimed_amount,
+            funded: airdrop.payer_id.is_some(),
+            payer_id: airdrop.payer_id,
+            claim_deadline: airdrop.claim_deadline,
+            reclaimed: airdrop.reclaimed,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct StatsView {
+    #[serde(with = "u128_dec_format")]
+    pub total_deposited: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_claimed: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_terminated: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_draft_refunded: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_airdrop_reclaimed: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_value_locked: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub pending_draft_refunds: Balance,
+    pub active_lockups: u64,
+    pub active_accounts: u64,
+}
+
+impl From<&Stats> for StatsView {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            total_deposited: stats.total_deposited,
+            total_claimed: stats.total_claimed,
+            total_terminated: stats.total_terminated,
+            total_draft_refunded: stats.total_draft_refunded,
+            total_airdrop_reclaimed: stats.total_airdrop_reclaimed,
+            total_value_locked: stats.total_value_locked(),
+            pending_draft_refunds: stats.pending_draft_refunds,
+            active_lockups: stats.active_lockups,
+            active_accounts: stats.active_accounts,
+        }
+    }
+}
+
+/// The positions of the `check_invariants` page in each collection.
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))]
+pub struct InvariantsIndex {
+    pub lockups: u32,
+    pub draft_groups: u32,
+    pub airdrops: u32,
+}
+
+/// The obligations of the contract within the page of lockups, draft groups and airdrops.
+/// The sum of all pages plus the pending draft refunds should match the total value locked.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct InvariantsView {
+    pub from_index: InvariantsIndex,
+    /// The start of the next page.
+    pub to_index: InvariantsIndex,
+    pub is_last_page: bool,
+    /// The unclaimed balance of lockups.
+    #[serde(with = "u128_dec_format")]
+    pub lockups_balance: Balance,
+    /// The balance of funded and partially funded draft groups.
+    #[serde(with = "u128_dec_format")]
+    pub draft_groups_balance: Balance,
+    /// The unclaimed balance of funded airdrops.
+    #[serde(with = "u128_dec_format")]
+    pub airdrops_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub pending_draft_refunds: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_value_locked: Balance,
+}
+
+#[derive(Serialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct LockupBalanceView {
+    pub timestamp: TimestampSec,
+    #[serde(with = "u128_dec_format")]
+    pub unlocked_balance: Balance,
+    /// The balance that can be claimed at the timestamp, given the current claimed balance.
+    #[serde(with = "u128_dec_format")]
+    pub claimable_balance: Balance,
+}
+
+impl LockupBalanceView {
+    fn new(lockup: &Lockup, timestamp: TimestampSec) -> Self {
+        let unlocked_balance = lockup.schedule.unlocked_balance(timestamp);
+        Self {
+            timestamp,
+            unlocked_balance,
+            claimable_balance: unlocked_balance.saturating_sub(lockup.claimed_balance),
+        }
+    }
+}
+
+#[derive(Serialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq, Deserialize))]
+pub struct AccountSummaryView {
+    pub num_lockups: u32,
+    #[serde(with = "u128_dec_format")]
+    pub total_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub locked_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub unlocked_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub claimed_balance: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub claimable_balance: Balance,
+    /// The vested balance of lockups with known vesting schedules.
+    #[serde(with = "u128_dec_format")]
+    pub vested_balance: Balance,
+    /// The unvested balance of lockups with known vesting schedules.
+    #[serde(with = "u128_dec_format")]
+    pub unvested_balance: Balance,
+    /// The total balance of lockups with hashed vesting schedules.
+    #[serde(with = "u128_dec_format")]
+    pub hidden_vesting_balance: Balance,
+    /// The earliest timestamp when one of the lockups unlocks more tokens.
+    pub next_unlock_timestamp: Option<TimestampSec>,
+    /// The timestamp of the summary
+    pub timestamp: TimestampSec,
+}
+
 #[near_bindgen]
 impl Contract {
     pub fn get_token_account_id(&self) -> ValidAccountId {
@@ -143,8 +394,109 @@ impl Contract {
             .collect()
     }
 
+    /// Returns the fully claimed and terminated lockups of the account in the order they were
+    /// archived. A lockup restored after a failed transfer leaves the gap filled by the last one.
+    pub fn get_account_lockup_history(
+        &self,
+        account_id: ValidAccountId,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        match self.account_archived_lockups.get(account_id.as_ref()) {
+            Some(lockup_ids) => page_lockup_ids(lockup_ids.as_vector(), from_index, limit)
+                .into_iter()
+                .map(|index| (index, self.internal_get_lockup(index).unwrap().into()))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Sums up the balances of the active account lockups at the given or the current timestamp.
+    /// The archived lockups are not included.
+    pub fn get_account_summary(
+        &self,
+        account_id: ValidAccountId,
+        timestamp: Option<TimestampSec>,
+    ) -> AccountSummaryView {
+        let timestamp = timestamp.unwrap_or_else(current_timestamp_sec);
+        let mut summary = AccountSummaryView {
+            timestamp,
+            ..Default::default()
+        };
+        for (_index, lockup) in self.internal_get_account_lockups(account_id.as_ref()) {
+            let total_balance = lockup.schedule.total_balance();
+            let unlocked_balance = lockup.schedule.unlocked_balance(timestamp);
+            summary.num_lockups += 1;
+            summary.total_balance += total_balance;
+            summary.unlocked_balance += unlocked_balance;
+            summary.locked_balance += total_balance - unlocked_balance;
+            summary.claimed_balance += lockup.claimed_balance;
+            summary.claimable_balance += unlocked_balance.saturating_sub(lockup.claimed_balance);
+            match lockup.vested_balance(timestamp) {
+                Some(vested_balance) => {
+                    summary.vested_balance += vested_balance;
+                    summary.unvested_balance += total_balance - vested_balance;
+                }
+                None => summary.hidden_vesting_balance += total_balance,
+            }
+            if let Some(next_unlock_timestamp) = lockup.schedule.next_unlock_timestamp(timestamp) {
+                summary.next_unlock_timestamp = Some(
+                    summary
+                        .next_unlock_timestamp
+                        .map_or(next_unlock_timestamp, |current| {
+                            std::cmp::min(current, next_unlock_timestamp)
+                        }),
+                );
+            }
+        }
+        summary
+    }
+
     pub fn get_lockup(&self, index: LockupIndex) -> Option<LockupView> {
-        self.lockups.get(index as _).map(|lockup| lockup.into())
+        self.internal_get_lockup(index).map(|lockup| lockup.into())
+    }
+
+    /// Returns the lockup balances at each of the given timestamps or at the current timestamp.
+    pub fn get_lockup_balances(
+        &self,
+        index: LockupIndex,
+        timestamps: Option<Vec<TimestampSec>>,
+    ) -> Option<Vec<LockupBalanceView>> {
+        self.internal_get_lockup(index).map(|lockup| {
+            timestamps
+                .unwrap_or_else(|| vec![current_timestamp_sec()])
+                .into_iter()
+                .map(|timestamp| LockupBalanceView::new(&lockup, timestamp))
+                .collect()
+        })
+    }
+
+    /// Returns the total balances of the active account lockups at each of the given timestamps
+    /// or at the current timestamp. The archived lockups are not included.
+    pub fn get_account_balances(
+        &self,
+        account_id: ValidAccountId,
+        timestamps: Option<Vec<TimestampSec>>,
+    ) -> Vec<LockupBalanceView> {
+        let lockups = self.internal_get_account_lockups(account_id.as_ref());
+        timestamps
+            .unwrap_or_else(|| vec![current_timestamp_sec()])
+            .into_iter()
+            .map(|timestamp| {
+                lockups.iter().fold(
+                    LockupBalanceView {
+                        timestamp,
+                        ..Default::default()
+                    },
+                    |mut total, (_index, lockup)| {
+                        let balance = LockupBalanceView::new(lockup, timestamp);
+                        total.unlocked_balance += balance.unlocked_balance;
+                        total.claimable_balance += balance.claimable_balance;
+                        total
+                    },
+                )
+            })
+            .collect()
     }
 
     pub fn get_lockups(&self, indices: Vec<LockupIndex>) -> Vec<(LockupIndex, LockupView)> {
@@ -170,6 +522,184 @@ impl Contract {
             .collect()
     }
 
+    /// Returns up to `limit` lockups matching the filter in the given order, ascending by
+    /// the lockup index by default, starting from the `from_index` cursor.
+    ///
+    /// When sorted by the lockup index, at most `LOCKUP_SEARCH_SCAN_LIMIT` lockups are scanned
+    /// per call, so the page can be shorter than the limit while `next_index` is set.
+    /// Sorting by the unlock start or the total balance requires the `account_id` filter,
+    /// since all the active and archived lockups of the account are sorted in one call.
+    pub fn find_lockups(
+        &self,
+        filter: LockupFilter,
+        sort: Option<LockupSort>,
+        from_index: Option<LockupIndex>,
+        limit: Option<u32>,
+    ) -> LockupSearchView {
+        let sort = sort.unwrap_or_default();
+        let limit = limit.unwrap_or(LOCKUP_SEARCH_SCAN_LIMIT) as usize;
+        if sort.key != LockupSortKey::Index {
+            return self.internal_find_account_lockups(&filter, sort, from_index, limit);
+        }
+
+        let scan_limit = LOCKUP_SEARCH_SCAN_LIMIT as usize;
+        let candidates: Vec<LockupIndex> = match &filter.creator_id {
+            Some(creator_id) => match self.creator_lockups.get(creator_id.as_ref()) {
+                Some(lockup_ids) => {
+                    if sort.descending {
+                        let end = from_index.map_or(lockup_ids.len(), |from_index| {
+                            count_lockup_ids_below(&lockup_ids, from_index as u64 + 1)
+                        });
+                        (0..end)
+                            .rev()
+                            .take(scan_limit + 1)
+                            .map(|position| lockup_ids.get(position).unwrap())
+                            .collect()
+                    } else {
+                        let start =
+                            count_lockup_ids_below(&lockup_ids, from_index.unwrap_or(0) as u64);
+                        (start..lockup_ids.len())
+                            .take(scan_limit + 1)
+                            .map(|position| lockup_ids.get(position).unwrap())
+                            .collect()
+                    }
+                }
+                None => vec![],
+            },
+            None => {
+                let num_lockups = self.get_num_lockups();
+                if sort.descending {
+                    let end = from_index.map_or(num_lockups, |from_index| {
+                        std::cmp::min(num_lockups, from_index.saturating_add(1))
+                    });
+                    (0..end).rev().take(scan_limit + 1).collect()
+                } else {
+                    (from_index.unwrap_or(0)..num_lockups)
+                        .take(scan_limit + 1)
+                        .collect()
+                }
+            }
+        };
+
+        let mut lockups = vec![];
+        let mut next_index = None;
+        for (scanned, index) in candidates.into_iter().enumerate() {
+            if lockups.len() == limit || scanned == scan_limit {
+                next_index = Some(index);
+                break;
+            }
+            let lockup = self.internal_get_lockup(index).unwrap();
+            if lockup.matches(&filter) {
+                lockups.push((index, lockup.into()));
+            }
+        }
+
+        LockupSearchView {
+            lockups,
+            next_index,
+        }
+    }
+
+    /// Returns the lockups funded by the given account, ordered by the lockup index.
+    pub fn get_lockups_by_creator(
+        &self,
+        creator_id: ValidAccountId,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        match self.creator_lockups.get(creator_id.as_ref()) {
+            Some(lockup_ids) => page_lockup_ids(&lockup_ids, from_index, limit)
+                .into_iter()
+                .map(|index| (index, self.internal_get_lockup(index).unwrap().into()))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Returns the lockups that can be terminated by the given beneficiary in the order
+    /// they were added. A termination moves the last lockup into the place of the terminated one.
+    pub fn get_terminable_lockups(
+        &self,
+        beneficiary_id: ValidAccountId,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        let lockup_ids = match self.beneficiary_lockups.get(beneficiary_id.as_ref()) {
+            Some(lockup_ids) => page_lockup_ids(lockup_ids.as_vector(), from_index, limit),
+            None => vec![],
+        };
+        lockup_ids
+            .into_iter()
+            .map(|index| (index, self.internal_get_lockup(index).unwrap().into()))
+            .collect()
+    }
+
+    /// Returns the latest claims, refunds and terminations of the lockup, oldest first.
+    pub fn get_lockup_history(
+        &self,
+        index: LockupIndex,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<LockupEvent> {
+        self.lockup_history
+            .get(&index)
+            .unwrap_or_default()
+            .into_iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect()
+    }
+
+    pub fn get_lockup_history_limit(&self) -> u32 {
+        self.lockup_history_limit
+    }
+
+    pub fn get_stats(&self) -> StatsView {
+        (&self.stats).into()
+    }
+
+    /// Sums the obligations within the page. Each collection is paged by its own index,
+    /// up to `limit` items of each.
+    pub fn check_invariants(
+        &self,
+        from_index: Option<InvariantsIndex>,
+        limit: Option<u32>,
+    ) -> InvariantsView {
+        let from_index = from_index.unwrap_or_default();
+        let page_end = |from_index: u32, len: u64| {
+            std::cmp::min(
+                len,
+                from_index as u64 + limit.map_or(len, |limit| limit as u64),
+            ) as u32
+        };
+        let to_index = InvariantsIndex {
+            lockups: page_end(from_index.lockups, self.lockups.len()),
+            draft_groups: page_end(from_index.draft_groups, self.draft_groups.len()),
+            airdrops: page_end(from_index.airdrops, self.airdrops.len()),
+        };
+        let lockups_balance =
+            self.internal_lockups_balance(from_index.lockups as _..to_index.lockups as _);
+        let draft_groups_balance = self.internal_draft_groups_balance(
+            from_index.draft_groups as _..to_index.draft_groups as _,
+        );
+        let airdrops_balance =
+            self.internal_airdrops_balance(from_index.airdrops as _..to_index.airdrops as _);
+        let is_last_page = to_index.lockups as u64 >= self.lockups.len()
+            && to_index.draft_groups as u64 >= self.draft_groups.len()
+            && to_index.airdrops as u64 >= self.airdrops.len();
+
+        InvariantsView {
+            from_index,
+            to_index,
+            is_last_page,
+            lockups_balance,
+            draft_groups_balance,
+            airdrops_balance,
+            pending_draft_refunds: self.stats.pending_draft_refunds,
+            total_value_locked: self.stats.total_value_locked(),
+        }
+    }
+
     pub fn get_deposit_whitelist(&self) -> Vec<AccountId> {
         self.deposit_whitelist.to_vec()
     }
@@ -191,6 +721,14 @@ impl Contract {
         }
     }
 
+    pub fn get_fractional_schedule(
+        &self,
+        schedule: FractionalSchedule,
+        total_balance: WrappedBalance,
+    ) -> Schedule {
+        schedule.into_schedule(total_balance.0)
+    }
+
     pub fn get_next_draft_group_id(&self) -> DraftGroupIndex {
         self.next_draft_group_id
     }
@@ -227,8 +765,44 @@ impl Contract {
             .collect()
     }
 
+    pub fn get_draft_groups_filtered(
+        &self,
+        filter: DraftGroupFilter,
+        // not the draft_id, but internal index used inside the LookupMap struct
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(DraftGroupIndex, DraftGroupView)> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(self.draft_groups.len() as _);
+        self.draft_groups
+            .iter()
+            .skip(from_index as _)
+            .take(limit as _)
+            .filter(|(_, draft_group)| draft_group.matches(&filter))
+            .map(|(index, draft_group)| (index, draft_group.into()))
+            .collect()
+    }
+
+    /// Returns draft groups that have not been funded before the deadline.
+    pub fn get_expired_draft_groups(
+        &self,
+        // not the draft_id, but internal index used inside the LookupMap struct
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(DraftGroupIndex, DraftGroupView)> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(self.draft_groups.len() as _);
+        self.draft_groups
+            .iter()
+            .skip(from_index as _)
+            .take(limit as _)
+            .filter(|(_, draft_group)| draft_group.is_expired())
+            .map(|(index, draft_group)| (index, draft_group.into()))
+            .collect()
+    }
+
     pub fn get_draft(&self, index: DraftIndex) -> Option<DraftView> {
-        self.drafts.get(&index as _).map(|draft| draft.into())
+        self.internal_get_draft(index).map(|draft| draft.into())
     }
 
     pub fn get_drafts(&self, indices: Vec<DraftIndex>) -> Vec<(DraftIndex, DraftView)> {
@@ -237,4 +811,188 @@ impl Contract {
             .filter_map(|index| self.get_draft(index).map(|draft| (index, draft)))
             .collect()
     }
+
+    pub fn get_draft_refunds(&self, account_id: ValidAccountId) -> WrappedBalance {
+        self.draft_refunds
+            .get(account_id.as_ref())
+            .unwrap_or(0)
+            .into()
+    }
+
+    pub fn get_drafts_by_group(
+        &self,
+        draft_group_id: DraftGroupIndex,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(DraftIndex, DraftView)> {
+        let draft_group = self
+            .draft_groups
+            .get(&draft_group_id as _)
+            .expect("draft group not found");
+        let mut draft_ids: Vec<DraftIndex> = draft_group.draft_indices.into_iter().collect();
+        draft_ids.sort_unstable();
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(draft_ids.len() as _);
+        draft_ids
+            .into_iter()
+            .skip(from_index as _)
+            .take(limit as _)
+            .filter_map(|index| self.get_draft(index).map(|draft| (index, draft)))
+            .collect()
+    }
+
+    /// Returns not converted drafts with the given lockup receiver.
+    pub fn get_drafts_by_account(
+        &self,
+        account_id: ValidAccountId,
+    ) -> Vec<(DraftIndex, DraftView)> {
+        let mut draft_ids: Vec<DraftIndex> = self
+            .account_drafts
+            .get(account_id.as_ref())
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        draft_ids.sort_unstable();
+        self.get_drafts(draft_ids)
+    }
+
+    pub fn get_num_airdrops(&self) -> u32 {
+        self.airdrops.len() as _
+    }
+
+    pub fn get_airdrop(&self, index: AirdropIndex) -> Option<AirdropView> {
+        self.airdrops.get(index as _).map(|airdrop| airdrop.into())
+    }
+
+    pub fn is_airdrop_claimed(&self, index: AirdropIndex, account_id: ValidAccountId) -> bool {
+        self.airdrop_claims
+            .contains(&(index, account_id.as_ref().clone()))
+    }
+
+    pub fn hash_airdrop_leaf(
+        &self,
+        account_id: ValidAccountId,
+        amount: WrappedBalance,
+    ) -> Base58CryptoHash {
+        airdrop_leaf_hash(account_id.as_ref(), amount.0).into()
+    }
+
+    pub fn get_schedule_template(&self, template_id: TemplateId) -> Option<ScheduleTemplate> {
+        self.schedule_templates.get(&template_id)
+    }
+
+    pub fn get_schedule_templates_paged(
+        &self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(TemplateId, ScheduleTemplate)> {
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(self.schedule_templates.len() as _);
+        self.schedule_templates
+            .iter()
+            .skip(from_index as _)
+            .take(limit as _)
+            .collect()
+    }
+}
+
+impl Contract {
+    /// Sorts the active and archived lockups of the filtered account by the sort key.
+    /// The cursor is the lockup index to continue from, even if it no longer matches the filter.
+    fn internal_find_account_lockups(
+        &self,
+        filter: &LockupFilter,
+        sort: LockupSort,
+        from_index: Option<LockupIndex>,
+        limit: usize,
+    ) -> LockupSearchView {
+        let account_id: &AccountId = filter
+            .account_id
+            .as_ref()
+            .expect("Sorting by the unlock start or the total balance requires the account_id")
+            .as_ref();
+        let mut lockup_ids: Vec<LockupIndex> = self
+            .account_lockups
+            .get(account_id)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        if let Some(archive) = self.account_archived_lockups.get(account_id) {
+            lockup_ids.extend(archive.iter());
+        }
+
+        let mut lockups: Vec<(Balance, LockupIndex, Lockup)> = lockup_ids
+            .into_iter()
+            .map(|index| (index, self.internal_get_lockup(index).unwrap()))
+            .filter(|(_index, lockup)| lockup.matches(filter))
+            .map(|(index, lockup)| (lockup.sort_value(sort.key), index, lockup))
+            .collect();
+        lockups.sort_unstable_by_key(|(value, index, _lockup)| (*value, *index));
+        if sort.descending {
+            lockups.reverse();
+        }
+
+        let start = match from_index {
+            Some(from_index) => {
+                let cursor = (
+                    self.internal_get_lockup(from_index)
+                        .expect("lockup not found")
+                        .sort_value(sort.key),
+                    from_index,
+                );
+                lockups
+                    .iter()
+                    .position(|(value, index, _lockup)| {
+                        if sort.descending {
+                            (*value, *index) <= cursor
+                        } else {
+                            (*value, *index) >= cursor
+                        }
+                    })
+                    .unwrap_or(lockups.len())
+            }
+            None => 0,
+        };
+        let mut page = lockups.into_iter().skip(start);
+        let lockups = page
+            .by_ref()
+            .take(limit)
+            .map(|(_value, index, lockup)| (index, lockup.into()))
+            .collect();
+
+        LockupSearchView {
+            lockups,
+            next_index: page.next().map(|(_value, index, _lockup)| index),
+        }
+    }
+}
+
+/// Returns the number of the lockup IDs below the bound in the ascending index.
+fn count_lockup_ids_below(lockup_ids: &Vector<LockupIndex>, bound: u64) -> u64 {
+    let (mut low, mut high) = (0, lockup_ids.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if (lockup_ids.get(mid).unwrap() as u64) < bound {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Reads the page of lockup IDs without loading the rest of the index.
+fn page_lockup_ids(
+    lockup_ids: &Vector<LockupIndex>,
+    from_index: Option<u32>,
+    limit: Option<u32>,
+) -> Vec<LockupIndex> {
+    let from_index = from_index.unwrap_or(0) as u64;
+    let to_index = std::cmp::min(
+        lockup_ids.len(),
+        from_index + limit.map_or(lockup_ids.len(), |limit| limit as u64),
+    );
+    (from_index..to_index)
+        .map(|position| lockup_ids.get(position).unwrap())
+        .collect()
 }
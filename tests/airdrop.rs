@@ -0,0 +1,206 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_airdrop_claim() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, _vesting_schedule) = lockup_vesting_schedule(d(1, TOKEN_DECIMALS));
+    let alice_amount = amount;
+    let bob_amount = amount / 2;
+    let charlie_amount = amount / 4;
+    let total_amount = alice_amount + bob_amount + charlie_amount;
+
+    let alice_leaf = airdrop_leaf(&users.alice, alice_amount);
+    assert_eq!(
+        e.hash_airdrop_leaf(&users.alice, alice_amount),
+        Base58CryptoHash::from(alice_leaf)
+    );
+    let bob_leaf = airdrop_leaf(&users.bob, bob_amount);
+    let charlie_leaf = airdrop_leaf(&users.charlie, charlie_amount);
+    let alice_bob_node = merkle_node(alice_leaf, bob_leaf);
+    let merkle_root = merkle_node(alice_bob_node, charlie_leaf);
+
+    let airdrop = AirdropCreate {
+        merkle_root: merkle_root.into(),
+        schedule: lockup_schedule.clone(),
+        total_amount: total_amount.into(),
+        claim_deadline: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+    };
+
+    // create by not authorized account
+    let res = e.create_airdrop(&users.alice, &airdrop);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    let res = e.create_airdrop(&e.owner, &airdrop);
+    assert!(res.is_ok());
+    let airdrop_id: AirdropIndex = res.unwrap_json();
+    assert_eq!(airdrop_id, 0);
+
+    // claim from not funded airdrop
+    let alice_proof: Vec<Base58CryptoHash> = vec![bob_leaf.into(), charlie_leaf.into()];
+    let res = e.claim_airdrop(&users.alice, airdrop_id, alice_amount, &alice_proof);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("cannot claim from not funded airdrop"));
+
+    // fund with wrong amount
+    let res = e.fund_airdrop(&e.owner, amount, airdrop_id);
+    assert!(res.logs()[0].contains("Refund"));
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, 0);
+
+    let res = e.fund_airdrop(&e.owner, total_amount, airdrop_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, total_amount);
+    let view = e.get_airdrop(airdrop_id).unwrap();
+    assert!(view.funded);
+    assert_eq!(view.payer_id, Some(e.owner.valid_account_id()));
+    assert_eq!(view.total_amount, total_amount);
+    assert_eq!(view.claimed_amount, 0);
+
+    // wrong amount
+    let res = e.claim_airdrop(&users.alice, airdrop_id, alice_amount + 1, &alice_proof);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Invalid Merkle proof"));
+
+    // somebody else's leaf
+    let res = e.claim_airdrop(&users.dude, airdrop_id, alice_amount, &alice_proof);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Invalid Merkle proof"));
+
+    // alice claims
+    let res = e.claim_airdrop(&users.alice, airdrop_id, alice_amount, &alice_proof);
+    assert!(res.is_ok());
+    let lockup_index: LockupIndex = res.unwrap_json();
+    assert!(e.is_airdrop_claimed(airdrop_id, &users.alice));
+    assert!(!e.is_airdrop_claimed(airdrop_id, &users.bob));
+
+    let lockup = e.get_lockup(lockup_index);
+    assert_eq!(lockup.account_id, users.alice.valid_account_id());
+    assert_eq!(lockup.total_balance, alice_amount);
+    assert_eq!(lockup.schedule, lockup_schedule.scale(alice_amount));
+    assert!(lockup.termination_config.is_none());
+
+    // double claim
+    let res = e.claim_airdrop(&users.alice, airdrop_id, alice_amount, &alice_proof);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("airdrop already claimed"));
+
+    // charlie claims with a shorter proof
+    let charlie_proof: Vec<Base58CryptoHash> = vec![alice_bob_node.into()];
+    let res = e.claim_airdrop(&users.charlie, airdrop_id, charlie_amount, &charlie_proof);
+    assert!(res.is_ok());
+    let lockups = e.get_account_lockups(&users.charlie);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, charlie_amount);
+
+    let view = e.get_airdrop(airdrop_id).unwrap();
+    assert_eq!(view.claimed_amount, alice_amount + charlie_amount);
+
+    // unlock and claim tokens
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1);
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, alice_amount);
+    assert_eq!(e.ft_balance_of(&users.alice), alice_amount);
+}
+
+#[test]
+fn test_airdrop_reclaim() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, _vesting_schedule) = lockup_vesting_schedule(d(1, TOKEN_DECIMALS));
+    let alice_leaf = airdrop_leaf(&users.alice, amount);
+    let bob_leaf = airdrop_leaf(&users.bob, amount);
+    let merkle_root = merkle_node(alice_leaf, bob_leaf);
+    let claim_deadline = GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC;
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount * 3, &users.eve);
+
+    // the deadline should be in the future
+    let res = e.create_airdrop(
+        &users.eve,
+        &AirdropCreate {
+            merkle_root: merkle_root.into(),
+            schedule: lockup_schedule.clone(),
+            total_amount: (amount * 3).into(),
+            claim_deadline: GENESIS_TIMESTAMP_SEC,
+        },
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("expected claim deadline in the future"));
+
+    // the total amount exceeds the leaves by one amount
+    let airdrop_id: AirdropIndex = e
+        .create_airdrop(
+            &users.eve,
+            &AirdropCreate {
+                merkle_root: merkle_root.into(),
+                schedule: lockup_schedule.clone(),
+                total_amount: (amount * 3).into(),
+                claim_deadline,
+            },
+        )
+        .unwrap_json();
+
+    // cannot reclaim not funded airdrop
+    let res = e.reclaim_airdrop(&users.eve, airdrop_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("cannot reclaim not funded airdrop"));
+
+    let balance: WrappedBalance = e
+        .fund_airdrop(&users.eve, amount * 3, airdrop_id)
+        .unwrap_json();
+    assert_eq!(balance.0, amount * 3);
+    assert_eq!(e.ft_balance_of(&users.eve), 0);
+
+    let res = e.claim_airdrop(&users.alice, airdrop_id, amount, &vec![bob_leaf.into()]);
+    assert!(res.is_ok());
+
+    // cannot reclaim before the deadline
+    let res = e.reclaim_airdrop(&users.eve, airdrop_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The airdrop claim deadline has not passed"));
+
+    e.set_time_sec(claim_deadline);
+
+    // cannot claim after the deadline
+    let res = e.claim_airdrop(&users.bob, airdrop_id, amount, &vec![alice_leaf.into()]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The airdrop claim deadline has passed"));
+
+    // only the payer can reclaim
+    let res = e.reclaim_airdrop(&e.owner, airdrop_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the airdrop payer"));
+
+    let res: WrappedBalance = e.reclaim_airdrop(&users.eve, airdrop_id).unwrap_json();
+    assert_eq!(res.0, amount * 2);
+    assert_eq!(e.ft_balance_of(&users.eve), amount * 2);
+    let view = e.get_airdrop(airdrop_id).unwrap();
+    assert!(view.reclaimed);
+    assert_eq!(view.claimed_amount, amount);
+    let stats = e.get_stats();
+    assert_eq!(stats.total_airdrop_reclaimed, amount * 2);
+    assert_eq!(stats.total_value_locked, amount);
+
+    let res = e.reclaim_airdrop(&users.eve, airdrop_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("airdrop already reclaimed"));
+
+    // the claimed lockup is not affected
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount);
+}
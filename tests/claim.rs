@@ -501,3 +501,131 @@ fn test_claim_specific_lockups_overflow() {
     assert!(!res.is_ok());
     assert!(format!("{:?}", res.status()).contains("attempt to add with overflow"));
 }
+
+#[test]
+fn test_fractional_lockup_claim() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS) + 1;
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = fractional_lockup_vesting_schedule();
+    let fractional_lockup_create = FractionalLockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule.clone(),
+        vesting_schedule: Some(vesting_schedule.clone()),
+    };
+    let balance: WrappedBalance = e
+        .add_fractional_lockup(&e.owner, amount, &fractional_lockup_create)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount);
+    assert_eq!(lockups[0].1.schedule.0[1].balance, amount * 3 / 4);
+    assert_eq!(
+        lockups[0]
+            .1
+            .termination_config
+            .as_ref()
+            .unwrap()
+            .beneficiary_id,
+        e.owner.valid_account_id()
+    );
+
+    // Rewind to 4Y + 1, fully unlocked
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1);
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&users.alice), amount);
+}
+
+#[test]
+fn test_lockup_history() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let schedule = Schedule(vec![
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC,
+            balance: 0,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            balance: amount,
+        },
+    ]);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: schedule.clone(),
+        vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    assert!(e.get_lockup_history(0, None, None).is_empty());
+
+    // the claim without storage deposit is refunded
+    let time_1 = GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC / 4;
+    e.set_time_sec(time_1);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, 0);
+
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let time_2 = GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC / 2;
+    e.set_time_sec(time_2);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount / 2);
+
+    let time_3 = GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3 / 4;
+    e.set_time_sec(time_3);
+    let res: WrappedBalance = e.terminate(&e.owner, 0).unwrap_json();
+    assert_eq!(res.0, amount / 4);
+
+    let history = e.get_lockup_history(0, None, None);
+    assert_eq!(
+        history
+            .iter()
+            .map(|event| (event.kind, event.amount))
+            .collect::<Vec<_>>(),
+        vec![
+            (LockupEventKind::Claim, amount / 4),
+            (LockupEventKind::Refund, amount / 4),
+            (LockupEventKind::Claim, amount / 2),
+            (LockupEventKind::Termination, amount / 4),
+        ]
+    );
+    assert!(history[0].timestamp >= time_1 && history[1].timestamp < time_2);
+    assert!(history[2].timestamp >= time_2 && history[2].timestamp < time_3);
+    assert!(history[3].timestamp >= time_3);
+    let res = e.get_lockup_history(0, Some(2), Some(1));
+    assert_eq!(res, history[2..3].to_vec());
+
+    // only whitelisted accounts can change the limit
+    let res = e.set_lockup_history_limit(&users.alice, 1);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    // the oldest events are dropped above the limit
+    let res = e.set_lockup_history_limit(&e.owner, 2);
+    assert!(res.is_ok());
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount / 4);
+    let res = e.get_lockup_history(0, None, None);
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0], history[3]);
+    assert_eq!(res[1].kind, LockupEventKind::Claim);
+    assert_eq!(res[1].amount, amount / 4);
+
+    // the history is disabled with the zero limit
+    let res = e.set_lockup_history_limit(&e.owner, 0);
+    assert!(res.is_ok());
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    assert!(e.get_lockup_history(1, None, None).is_empty());
+}
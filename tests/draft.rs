@@ -69,6 +69,8 @@ fn test_create_draft() {
     let draft = Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     let res = e.create_draft(&e.owner, &draft);
@@ -101,6 +103,77 @@ fn test_create_draft() {
     assert_eq!(res.total_amount, amount * 2);
 }
 
+#[test]
+fn test_draft_group_operators() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.dude.valid_account_id());
+    assert!(res.is_ok());
+
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(res.creator_id, e.owner.valid_account_id());
+    assert!(res.operators.is_empty());
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let draft = Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
+    };
+
+    // whitelisted account cannot add drafts to another account's group
+    let res = e.create_draft(&users.eve, &draft);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not a draft group operator"));
+
+    // only the creator can manage operators
+    let res = e.add_draft_group_operator(&users.eve, draft_group_id, &users.eve.valid_account_id());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group creator"));
+
+    let res = e.add_draft_group_operator(&e.owner, draft_group_id, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(res.operators, vec![users.eve.account_id.clone()]);
+
+    // operator can add drafts
+    let res = e.create_draft(&users.eve, &draft);
+    assert!(res.is_ok());
+
+    // operator cannot manage operators
+    let res =
+        e.add_draft_group_operator(&users.eve, draft_group_id, &users.dude.valid_account_id());
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group creator"));
+
+    // not an operator cannot discard
+    let res = e.discard_draft_group(&users.dude, draft_group_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not a draft group operator"));
+
+    // removed operator loses access
+    let res =
+        e.remove_draft_group_operator(&e.owner, draft_group_id, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    let res = e.create_draft(&users.eve, &draft);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not a draft group operator"));
+
+    let res = e.add_draft_group_operator(&e.owner, draft_group_id, &users.dude.valid_account_id());
+    assert!(res.is_ok());
+    // operator can discard
+    let res = e.discard_draft_group(&users.dude, draft_group_id);
+    assert!(res.is_ok());
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert!(res.discarded);
+}
+
 #[test]
 fn test_create_draft_with_zero_amount_fails() {
     let e = Env::init(None);
@@ -112,6 +185,8 @@ fn test_create_draft_with_zero_amount_fails() {
     let draft = Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     assert!(e.create_draft_group(&e.owner).is_ok());
@@ -136,6 +211,8 @@ fn test_create_drafts_batch() {
             Draft {
                 draft_group_id,
                 lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+                metadata: None,
+                created_at: 0,
             }
         })
         .collect();
@@ -174,6 +251,8 @@ fn test_fund_draft_group() {
     let draft = Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     e.create_draft_group(&e.owner);
@@ -194,8 +273,8 @@ fn test_fund_draft_group() {
     let balance: WrappedBalance = res.unwrap_json();
     assert_eq!(balance.0, 0);
 
-    // fund with wrong amount
-    let res = e.fund_draft_group(&e.owner, amount, 0);
+    // fund with exceeding amount
+    let res = e.fund_draft_group(&e.owner, amount * 3, 0);
     assert!(res.logs()[0].contains("Refund"));
     let balance: WrappedBalance = res.unwrap_json();
     assert_eq!(balance.0, 0);
@@ -231,6 +310,8 @@ fn test_convert_draft() {
     let draft = Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     assert_eq!(e.get_next_draft_group_id(), 0);
@@ -332,6 +413,8 @@ fn test_convert_drafts_batch() {
     let build_draft = |draft_group_id, user: &UserAccount| Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     let group_0: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
@@ -386,6 +469,8 @@ fn test_view_drafts() {
     let draft = Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     e.create_draft_group(&e.owner);
@@ -419,6 +504,8 @@ fn test_create_via_draft_batches_and_claim() {
     let draft = Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     e.create_draft_group(&e.owner);
@@ -478,8 +565,13 @@ fn test_draft_payer_update() {
     let draft = Draft {
         draft_group_id,
         lockup_create,
+        metadata: None,
+        created_at: 0,
     };
-    e.create_draft(&users.eve, &draft);
+    let res = e.add_draft_group_operator(&e.owner, draft_group_id, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    let res = e.create_draft(&users.eve, &draft);
+    assert!(res.is_ok());
 
     // fund draft group
     let res = e.fund_draft_group(&users.dude, amount, 0);
@@ -558,6 +650,8 @@ fn test_delete_draft_group_before_fund() {
     let draft = Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     // create draft 0
@@ -602,8 +696,12 @@ fn test_delete_draft_group_before_fund() {
     assert!(!res.is_ok());
     assert!(format!("{:?}", res.status()).contains("draft group is discarded"));
 
-    // anyone can delete draft after the group is discarded
+    // only the creator can delete drafts after the group is discarded
     let res = e.delete_drafts(&users.eve, vec![draft_id_0]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group creator"));
+
+    let res = e.delete_drafts(&e.owner, vec![draft_id_0]);
     assert!(res.is_ok());
     // first draft is removed
     let res = e.get_draft(draft_id_0);
@@ -615,7 +713,7 @@ fn test_delete_draft_group_before_fund() {
     assert_eq!(res.total_amount, amount, "expected total amount to decrease after draft delete");
 
     // deleting last draft
-    let res = e.delete_drafts(&users.eve, vec![draft_id_1]);
+    let res = e.delete_drafts(&e.owner, vec![draft_id_1]);
     assert!(res.is_ok());
     // last draft is removed
     let res = e.get_draft(draft_id_1);
@@ -646,6 +744,8 @@ fn test_delete_draft_group_after_fund() {
     let draft = Draft {
         draft_group_id,
         lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
     };
 
     // create draft 0
@@ -664,3 +764,1050 @@ fn test_delete_draft_group_after_fund() {
     assert!(!res.is_ok());
     assert!(format!("{:?}", res.status()).contains("draft group already funded"));
 }
+
+#[test]
+fn test_draft_conversion_policy() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let build_draft = |draft_group_id, user: &UserAccount| Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
+    };
+
+    let anyone_group: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let recipient_group: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let operators_group: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    assert_eq!(
+        e.get_draft_group(anyone_group).unwrap().conversion_policy,
+        DraftConversionPolicy::Anyone
+    );
+
+    // only the creator can set the policy
+    let res = e.set_draft_group_conversion_policy(
+        &users.eve,
+        recipient_group,
+        DraftConversionPolicy::Recipient,
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group creator"));
+
+    let res = e.set_draft_group_conversion_policy(
+        &e.owner,
+        recipient_group,
+        DraftConversionPolicy::Recipient,
+    );
+    assert!(res.is_ok());
+    let res = e.set_draft_group_conversion_policy(
+        &e.owner,
+        operators_group,
+        DraftConversionPolicy::Operators,
+    );
+    assert!(res.is_ok());
+    let res = e.add_draft_group_operator(&e.owner, operators_group, &users.dude.valid_account_id());
+    assert!(res.is_ok());
+    assert_eq!(
+        e.get_draft_group(recipient_group)
+            .unwrap()
+            .conversion_policy,
+        DraftConversionPolicy::Recipient
+    );
+
+    let draft_ids: Vec<DraftIndex> = e
+        .create_drafts(
+            &e.owner,
+            &vec![
+                build_draft(anyone_group, &users.alice),
+                build_draft(recipient_group, &users.alice),
+                build_draft(recipient_group, &users.bob),
+                build_draft(operators_group, &users.alice),
+                build_draft(operators_group, &users.bob),
+            ],
+        )
+        .unwrap_json();
+    assert_eq!(draft_ids, vec![0, 1, 2, 3, 4]);
+
+    for (draft_group_id, total_amount) in vec![
+        (anyone_group, amount),
+        (recipient_group, amount * 2),
+        (operators_group, amount * 2),
+    ] {
+        let res = e.fund_draft_group(&e.owner, total_amount, draft_group_id);
+        let balance: WrappedBalance = res.unwrap_json();
+        assert_eq!(balance.0, total_amount);
+    }
+
+    // policy cannot be changed after funding
+    let res = e.set_draft_group_conversion_policy(
+        &e.owner,
+        anyone_group,
+        DraftConversionPolicy::Operators,
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("group already funded"));
+
+    // anyone
+    let res = e.convert_draft(&users.eve, 0);
+    assert!(res.is_ok());
+
+    // recipient
+    let res = e.convert_draft(&users.eve, 1);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Only the draft recipient can convert"));
+    let res = e.convert_drafts(&users.alice, &vec![1, 2]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Only the draft recipient can convert"));
+    let res = e.convert_draft(&users.alice, 1);
+    assert!(res.is_ok());
+    let res = e.convert_draft(&users.bob, 2);
+    assert!(res.is_ok());
+
+    // operators
+    let res = e.convert_draft(&users.alice, 3);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not a draft group operator"));
+    let res = e.convert_draft(&e.owner, 3);
+    assert!(res.is_ok());
+    let res = e.convert_draft(&users.dude, 4);
+    assert!(res.is_ok());
+
+    assert_eq!(e.get_num_lockups(), 5);
+    assert_eq!(e.get_num_draft_groups(), 0);
+}
+
+#[test]
+fn test_draft_acceptance() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let build_draft = |draft_group_id, user: &UserAccount| Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
+    };
+    let terms_hash = Base58CryptoHash::from([1u8; 32]);
+    let acceptance = DraftAcceptance {
+        terms_hash: Some(terms_hash),
+        expiration_timestamp: GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC,
+    };
+
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+
+    // only the creator can require acceptance
+    let res = e.set_draft_group_acceptance(&users.eve, draft_group_id, Some(acceptance.clone()));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group creator"));
+
+    let res = e.set_draft_group_acceptance(&e.owner, draft_group_id, Some(acceptance.clone()));
+    assert!(res.is_ok());
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(res.acceptance, Some(acceptance.clone()));
+
+    let draft_ids: Vec<DraftIndex> = e
+        .create_drafts(
+            &e.owner,
+            &vec![
+                build_draft(draft_group_id, &users.alice),
+                build_draft(draft_group_id, &users.bob),
+                build_draft(draft_group_id, &users.charlie),
+            ],
+        )
+        .unwrap_json();
+    assert_eq!(draft_ids, vec![0, 1, 2]);
+
+    // only the receiver can accept
+    let res = e.accept_draft(&users.bob, 0, Some(terms_hash));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Only the draft recipient can accept"));
+
+    // wrong terms
+    let res = e.accept_draft(&users.alice, 0, Some(Base58CryptoHash::from([2u8; 32])));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The terms hash doesn't match"));
+    let res = e.accept_draft(&users.alice, 0, None);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("terms hash required"));
+
+    // accepting before funding
+    let res = e.accept_draft(&users.alice, 0, Some(terms_hash));
+    assert!(res.is_ok());
+    let res = e.accept_draft(&users.alice, 0, Some(terms_hash));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft already accepted"));
+
+    let res = e.fund_draft_group(&e.owner, amount * 3, draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount * 3);
+
+    // accepting after funding
+    let res = e.accept_draft(&users.bob, 1, Some(terms_hash));
+    assert!(res.is_ok());
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    let mut accepted_draft_indices = res.accepted_draft_indices;
+    accepted_draft_indices.sort();
+    assert_eq!(accepted_draft_indices, vec![0, 1]);
+
+    // cannot convert not accepted draft
+    let res = e.convert_draft(&users.eve, 2);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft is not accepted"));
+
+    let res = e.convert_draft(&users.eve, 0);
+    assert!(res.is_ok());
+
+    // cannot reclaim before expiration
+    let res = e.reclaim_drafts(&e.owner, vec![2]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft acceptance has not expired yet"));
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC + 1);
+
+    // cannot accept after expiration
+    let res = e.accept_draft(&users.charlie, 2, Some(terms_hash));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft acceptance has expired"));
+
+    // only the payer can reclaim
+    let res = e.reclaim_drafts(&users.eve, vec![2]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group payer"));
+
+    // accepted drafts cannot be reclaimed
+    let res = e.reclaim_drafts(&e.owner, vec![1]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("cannot reclaim accepted draft"));
+
+    let owner_balance = e.ft_balance_of(&e.owner);
+    let res: WrappedBalance = e.reclaim_drafts(&e.owner, vec![2]).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&e.owner), owner_balance + amount);
+    assert!(e.get_draft(2).is_none());
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(res.draft_indices, vec![1]);
+    assert_eq!(res.total_amount, amount);
+
+    // accepted draft can still be converted
+    let res = e.convert_draft(&users.eve, 1);
+    assert!(res.is_ok());
+    assert!(e.get_draft_group(draft_group_id).is_none());
+}
+
+#[test]
+fn test_cancel_funded_draft_group() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let build_draft = |draft_group_id, user: &UserAccount| Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
+    };
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount * 4, &users.eve);
+
+    let not_cancellable_group: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let res = e.create_drafts(
+        &e.owner,
+        &vec![
+            build_draft(not_cancellable_group, &users.alice),
+            build_draft(draft_group_id, &users.alice),
+            build_draft(draft_group_id, &users.bob),
+            build_draft(draft_group_id, &users.charlie),
+        ],
+    );
+    assert!(res.is_ok());
+
+    let res = e.fund_draft_group(&users.eve, amount, not_cancellable_group);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount);
+    let res = e.fund_cancellable_draft_group(
+        &users.eve,
+        amount * 3,
+        draft_group_id,
+        GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+    );
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount * 3);
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(
+        res.cancellable_after,
+        Some(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC)
+    );
+
+    // funded draft group without timelock cannot be cancelled
+    let res = e.cancel_draft_group(&users.eve, not_cancellable_group);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft group is not cancellable"));
+
+    // before the timelock
+    let res = e.cancel_draft_group(&users.eve, draft_group_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft group cannot be cancelled yet"));
+
+    // drafts cannot be reclaimed before the cancellation
+    let res = e.reclaim_drafts(&users.eve, vec![2]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft acceptance is not required"));
+
+    let res = e.convert_draft(&users.dude, 1);
+    assert!(res.is_ok());
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+
+    // only the payer can cancel
+    let res = e.cancel_draft_group(&e.owner, draft_group_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group payer"));
+
+    let res = e.cancel_draft_group(&users.eve, draft_group_id);
+    assert!(res.is_ok());
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert!(res.discarded);
+    assert_eq!(res.total_amount, amount * 2);
+
+    // cannot convert after the cancellation
+    let res = e.convert_draft(&users.dude, 2);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft group is discarded"));
+
+    // only the payer can withdraw
+    let res = e.reclaim_drafts(&e.owner, vec![2]);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group payer"));
+
+    // withdraw in batches
+    let res = e.reclaim_drafts(&users.eve, vec![2]);
+    // the refund is logged by the reclaim call before the transfer
+    assert!(res
+        .promise_results()
+        .into_iter()
+        .flatten()
+        .any(|outcome| outcome.logs().iter().any(|log| log.contains("Refunding"))));
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount);
+    assert_eq!(e.ft_balance_of(&users.eve), amount);
+    assert!(e.get_draft(2).is_none());
+
+    let res: WrappedBalance = e.reclaim_drafts(&users.eve, vec![3]).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert_eq!(e.ft_balance_of(&users.eve), amount * 2);
+    assert!(e.get_draft_group(draft_group_id).is_none());
+}
+
+#[test]
+fn test_edit_drafts_before_fund() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let other_draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let drafts = vec![
+        Draft {
+            draft_group_id,
+            lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+            metadata: None,
+            created_at: 0,
+        },
+        Draft {
+            draft_group_id,
+            lockup_create: LockupCreate::new_unlocked(users.bob.valid_account_id(), amount),
+            metadata: None,
+            created_at: 0,
+        },
+        Draft {
+            draft_group_id,
+            lockup_create: LockupCreate::new_unlocked(users.charlie.valid_account_id(), amount),
+            metadata: None,
+            created_at: 0,
+        },
+    ];
+    let res = e.create_drafts(&e.owner, &drafts);
+    assert!(res.is_ok());
+
+    // operators have to be in the deposit whitelist to edit drafts
+    for id in vec![draft_group_id, other_draft_group_id] {
+        let res = e.add_draft_group_operator(&e.owner, id, &users.eve.valid_account_id());
+        assert!(res.is_ok());
+    }
+    let lockup_create = LockupCreate::new_unlocked(users.dude.valid_account_id(), amount * 2);
+    let res = e.update_draft(&users.eve, 0, &lockup_create);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+    let res = e.remove_draft(&users.eve, 1);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+    let res = e.move_drafts(&users.eve, vec![2], other_draft_group_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+    assert_eq!(
+        e.get_draft_group(draft_group_id).unwrap().total_amount,
+        amount * 3
+    );
+
+    // fix the receiver and the amount of draft 0
+    let res = e.update_draft(&users.alice, 0, &lockup_create);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    let res = e.update_draft(&e.owner, 0, &lockup_create);
+    assert!(res.is_ok());
+    let draft = e.get_draft(0).unwrap();
+    assert_eq!(draft.draft_group_id, draft_group_id);
+    assert_eq!(
+        draft.lockup_create.account_id,
+        users.dude.valid_account_id()
+    );
+    assert_eq!(draft.lockup_create.schedule, lockup_create.schedule);
+    assert_eq!(draft.lockup_create.total_balance, amount * 2);
+    let draft_group = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(draft_group.total_amount, amount * 4);
+
+    // update with zero amount fails
+    let res = e.update_draft(
+        &e.owner,
+        0,
+        &LockupCreate::new_unlocked(users.dude.valid_account_id(), 0),
+    );
+    assert!(!res.is_ok());
+
+    // remove draft 1
+    let res = e.remove_draft(&users.alice, 1);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    let res = e.remove_draft(&e.owner, 1);
+    assert!(res.is_ok());
+    assert!(e.get_draft(1).is_none());
+    let draft_group = e.get_draft_group(draft_group_id).unwrap();
+    let mut draft_indices = draft_group.draft_indices;
+    draft_indices.sort();
+    assert_eq!(draft_indices, vec![0, 2]);
+    assert_eq!(draft_group.total_amount, amount * 3);
+
+    // move draft 2 to another group
+    let res = e.move_drafts(&e.owner, vec![2], draft_group_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft is already in the draft group"));
+
+    let res = e.move_drafts(&e.owner, vec![2], other_draft_group_id);
+    assert!(res.is_ok());
+    assert_eq!(e.get_draft(2).unwrap().draft_group_id, other_draft_group_id);
+    let draft_group = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(draft_group.draft_indices, vec![0]);
+    assert_eq!(draft_group.total_amount, amount * 2);
+    let other_draft_group = e.get_draft_group(other_draft_group_id).unwrap();
+    assert_eq!(other_draft_group.draft_indices, vec![2]);
+    assert_eq!(other_draft_group.total_amount, amount);
+
+    // drafts of the funded group cannot be edited
+    let res = e.fund_draft_group(&e.owner, amount * 2, draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount * 2);
+
+    let res = e.update_draft(&e.owner, 0, &lockup_create);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("group already funded"));
+
+    let res = e.remove_draft(&e.owner, 0);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("group already funded"));
+
+    let res = e.move_drafts(&e.owner, vec![0], other_draft_group_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("group already funded"));
+
+    let res = e.move_drafts(&e.owner, vec![2], draft_group_id);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("group already funded"));
+
+    // drafts of the discarded group cannot be edited
+    let res = e.discard_draft_group(&e.owner, other_draft_group_id);
+    assert!(res.is_ok());
+    let res = e.remove_draft(&e.owner, 2);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft group is discarded"));
+}
+
+#[test]
+fn test_draft_metadata() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let payroll_metadata = DraftMetadata {
+        name: Some("Payroll".to_string()),
+        memo: Some("January".to_string()),
+        external_id: Some("payroll-2022-01".to_string()),
+    };
+    let res = e.create_draft_group_with_metadata(&e.owner, Some(payroll_metadata.clone()));
+    let payroll_group_id: DraftGroupIndex = res.unwrap_json();
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + 100);
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    let res = e.create_draft_group_with_metadata(
+        &users.eve,
+        Some(DraftMetadata {
+            name: Some("Payroll".to_string()),
+            external_id: Some("payroll-2022-02".to_string()),
+            ..Default::default()
+        }),
+    );
+    let other_group_id: DraftGroupIndex = res.unwrap_json();
+    let unlabeled_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+
+    let draft_metadata = DraftMetadata {
+        external_id: Some("employee-42".to_string()),
+        ..Default::default()
+    };
+    let draft = Draft {
+        draft_group_id: payroll_group_id,
+        lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        metadata: Some(draft_metadata.clone()),
+        // ignored, set by the contract
+        created_at: 1,
+    };
+    let draft_id: DraftIndex = e.create_draft(&e.owner, &draft).unwrap_json();
+    let draft = e.get_draft(draft_id).unwrap();
+    assert_eq!(draft.metadata, Some(draft_metadata));
+    assert_eq!(draft.created_at, GENESIS_TIMESTAMP_SEC + 100);
+
+    let draft_group = e.get_draft_group(payroll_group_id).unwrap();
+    assert_eq!(draft_group.metadata, Some(payroll_metadata));
+    assert_eq!(draft_group.created_at, GENESIS_TIMESTAMP_SEC);
+    let draft_group = e.get_draft_group(unlabeled_group_id).unwrap();
+    assert!(draft_group.metadata.is_none());
+    assert_eq!(draft_group.created_at, GENESIS_TIMESTAMP_SEC + 100);
+
+    let ids = |groups: Vec<(DraftGroupIndex, DraftGroupView)>| -> Vec<DraftGroupIndex> {
+        groups.into_iter().map(|(index, _)| index).collect()
+    };
+
+    let res = e.get_draft_groups_filtered(&DraftGroupFilter::default(), None, None);
+    assert_eq!(
+        ids(res),
+        vec![payroll_group_id, other_group_id, unlabeled_group_id]
+    );
+
+    let filter = DraftGroupFilter {
+        name: Some("Payroll".to_string()),
+        ..Default::default()
+    };
+    let res = e.get_draft_groups_filtered(&filter, None, None);
+    assert_eq!(ids(res), vec![payroll_group_id, other_group_id]);
+
+    // paging goes over all draft groups
+    let res = e.get_draft_groups_filtered(&filter, Some(1), Some(2));
+    assert_eq!(ids(res), vec![other_group_id]);
+
+    let filter = DraftGroupFilter {
+        external_id: Some("payroll-2022-01".to_string()),
+        ..Default::default()
+    };
+    let res = e.get_draft_groups_filtered(&filter, None, None);
+    assert_eq!(ids(res), vec![payroll_group_id]);
+
+    let filter = DraftGroupFilter {
+        creator_id: Some(users.eve.valid_account_id()),
+        ..Default::default()
+    };
+    let res = e.get_draft_groups_filtered(&filter, None, None);
+    assert_eq!(ids(res), vec![other_group_id]);
+
+    let filter = DraftGroupFilter {
+        created_from: Some(GENESIS_TIMESTAMP_SEC + 1),
+        ..Default::default()
+    };
+    let res = e.get_draft_groups_filtered(&filter, None, None);
+    assert_eq!(ids(res), vec![other_group_id, unlabeled_group_id]);
+}
+
+#[test]
+fn test_partial_funding() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let build_draft = |draft_group_id, user: &UserAccount| Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
+    };
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount * 3, &users.eve);
+
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let discarded_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let res = e.create_drafts(
+        &e.owner,
+        &vec![
+            build_draft(draft_group_id, &users.alice),
+            build_draft(draft_group_id, &users.bob),
+            build_draft(draft_group_id, &users.charlie),
+            build_draft(discarded_group_id, &users.alice),
+        ],
+    );
+    assert!(res.is_ok());
+    let res =
+        e.set_draft_group_beneficiary(&e.owner, draft_group_id, Some(e.owner.valid_account_id()));
+    assert!(res.is_ok());
+
+    // the owner funds a third, eve funds the rest
+    let res = e.fund_cancellable_draft_group(
+        &e.owner,
+        amount,
+        draft_group_id,
+        GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+    );
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount);
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert!(!res.funded);
+    assert_eq!(res.funded_amount, amount);
+    assert_eq!(
+        res.contributions,
+        vec![(e.owner.account_id(), amount.into())]
+    );
+
+    // drafts cannot be added or converted while partially funded
+    let res = e.create_draft(&e.owner, &build_draft(draft_group_id, &users.dude));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("group is partially funded"));
+    let res = e.convert_draft(&users.alice, 0);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("cannot convert draft from not funded group"));
+
+    // exceeding the remaining amount
+    let res = e.fund_draft_group(&users.eve, amount * 3, draft_group_id);
+    assert!(res.logs()[0].contains("Refund"));
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, 0);
+
+    let res = e.fund_draft_group(&users.eve, amount * 2, draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount * 2);
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert!(res.funded);
+    assert_eq!(res.payer_id, Some(users.eve.valid_account_id()));
+    assert_eq!(res.funded_amount, amount * 3);
+    assert_eq!(
+        res.cancellable_after,
+        Some(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC)
+    );
+
+    let res = e.convert_draft(&users.alice, 0);
+    assert!(res.is_ok());
+
+    // the reclaimed drafts are refunded pro rata
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let res = e.cancel_draft_group(&users.eve, draft_group_id);
+    assert!(res.is_ok());
+    let owner_balance = e.ft_balance_of(&e.owner);
+    let eve_balance = e.ft_balance_of(&users.eve);
+    let res: WrappedBalance = e.reclaim_drafts(&users.eve, vec![1, 2]).unwrap_json();
+    assert_eq!(res.0, amount * 4 / 3);
+    assert_eq!(e.ft_balance_of(&users.eve), eve_balance + amount * 4 / 3);
+    assert_eq!(e.get_draft_refunds(&e.owner), amount * 2 / 3);
+    assert!(e.get_draft_group(draft_group_id).is_none());
+
+    let res: WrappedBalance = e.withdraw_draft_refunds(&e.owner).unwrap_json();
+    assert_eq!(res.0, amount * 2 / 3);
+    assert_eq!(e.ft_balance_of(&e.owner), owner_balance + amount * 2 / 3);
+    assert_eq!(e.get_draft_refunds(&e.owner), 0);
+
+    // the partial funding is refunded after the draft group is discarded
+    let res = e.fund_draft_group(&users.eve, amount / 2, discarded_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount / 2);
+    let res = e.discard_draft_group(&e.owner, discarded_group_id);
+    assert!(res.is_ok());
+    let res = e.get_draft_group(discarded_group_id).unwrap();
+    assert_eq!(res.funded_amount, 0);
+    assert!(res.contributions.is_empty());
+    assert_eq!(e.get_draft_refunds(&users.eve), amount / 2);
+
+    let res: WrappedBalance = e.withdraw_draft_refunds(&users.eve).unwrap_json();
+    assert_eq!(res.0, amount / 2);
+    assert_eq!(e.ft_balance_of(&users.eve), eve_balance + amount * 4 / 3);
+}
+
+#[test]
+fn test_multi_payer_draft_group_beneficiary() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let build_draft = |draft_group_id, user: &UserAccount| Draft {
+        draft_group_id,
+        lockup_create: LockupCreate {
+            account_id: user.valid_account_id(),
+            schedule: lockup_schedule.clone(),
+            vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule.clone())),
+        },
+        metadata: None,
+        created_at: 0,
+    };
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.dude.account_id);
+    e.ft_transfer(&e.owner, amount, &users.eve);
+
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let other_draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let res = e.create_drafts(
+        &e.owner,
+        &vec![
+            build_draft(draft_group_id, &users.alice),
+            build_draft(other_draft_group_id, &users.bob),
+        ],
+    );
+    assert!(res.is_ok());
+
+    // the second payer is refunded without the beneficiary
+    let res = e.fund_draft_group(&e.owner, amount / 2, draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount / 2);
+    let res = e.fund_draft_group(&users.eve, amount / 2, draft_group_id);
+    assert!(res.logs()[0].contains("Refund"));
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, 0);
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(res.funded_amount, amount / 2);
+
+    // the beneficiary cannot be changed after the funding has started
+    let res = e.set_draft_group_beneficiary(
+        &e.owner,
+        draft_group_id,
+        Some(users.dude.valid_account_id()),
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("group is partially funded"));
+
+    // only the creator sets the beneficiary
+    let res = e.set_draft_group_beneficiary(
+        &users.alice,
+        other_draft_group_id,
+        Some(users.dude.valid_account_id()),
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group creator"));
+    let res = e.set_draft_group_beneficiary(
+        &e.owner,
+        other_draft_group_id,
+        Some(users.dude.valid_account_id()),
+    );
+    assert!(res.is_ok());
+
+    let res = e.fund_draft_group(&e.owner, amount / 2, other_draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount / 2);
+    let res = e.fund_draft_group(&users.eve, amount / 2, other_draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount / 2);
+    let res = e.get_draft_group(other_draft_group_id).unwrap();
+    assert!(res.funded);
+    assert_eq!(res.payer_id, Some(users.eve.valid_account_id()));
+    assert_eq!(res.beneficiary_id, Some(users.dude.valid_account_id()));
+
+    // the lockup belongs to the beneficiary rather than the completing payer
+    let lockup_index: LockupIndex = e.convert_draft(&users.bob, 1).unwrap_json();
+    let lockup = e.get_lockup(lockup_index);
+    assert_eq!(lockup.creator_id, users.dude.valid_account_id());
+    assert_eq!(
+        lockup.termination_config.unwrap().beneficiary_id,
+        users.dude.valid_account_id()
+    );
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let res = e.terminate(&users.eve, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Unauthorized"));
+    let res = e.terminate(&e.owner, lockup_index);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Unauthorized"));
+
+    let res: WrappedBalance = e.terminate(&users.dude, lockup_index).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    assert_eq!(e.ft_balance_of(&users.dude), amount * 3 / 4);
+    assert_eq!(e.get_lockup(lockup_index).total_balance, amount / 4);
+}
+
+#[test]
+fn test_draft_group_funding_deadline() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let build_draft = |draft_group_id, user: &UserAccount| Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
+    };
+
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let funded_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let res = e.create_drafts(
+        &e.owner,
+        &vec![
+            build_draft(draft_group_id, &users.alice),
+            build_draft(draft_group_id, &users.bob),
+            build_draft(draft_group_id, &users.charlie),
+            build_draft(funded_group_id, &users.alice),
+        ],
+    );
+    assert!(res.is_ok());
+
+    let deadline = GENESIS_TIMESTAMP_SEC + ONE_DAY_SEC;
+    let res = e.set_draft_group_funding_deadline(&users.alice, draft_group_id, Some(deadline));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group creator"));
+
+    let res =
+        e.set_draft_group_funding_deadline(&e.owner, draft_group_id, Some(GENESIS_TIMESTAMP_SEC));
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("expected funding_deadline > now"));
+
+    for id in vec![draft_group_id, funded_group_id] {
+        let res = e.set_draft_group_funding_deadline(&e.owner, id, Some(deadline));
+        assert!(res.is_ok());
+    }
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert_eq!(res.funding_deadline, Some(deadline));
+    assert!(!res.expired);
+
+    // fund before the deadline, the first group only partially
+    let res = e.fund_draft_group(&e.owner, amount, draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount);
+    let res = e.fund_draft_group(&e.owner, amount, funded_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let res = e.cleanup_expired_draft_group(&users.dude, draft_group_id, None);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft group is not expired"));
+
+    e.set_time_sec(deadline + 1);
+    let res = e.get_expired_draft_groups(None, None);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0, draft_group_id);
+    assert!(res[0].1.expired);
+
+    // the funded draft group doesn't expire
+    let res = e.cleanup_expired_draft_group(&users.dude, funded_group_id, None);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("draft group is not expired"));
+
+    let res = e.fund_draft_group(&e.owner, amount * 2, draft_group_id);
+    assert!(res.logs()[0].contains("Refund"));
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, 0);
+
+    // anyone can clean up in batches
+    let res = e.cleanup_expired_draft_group(&users.dude, draft_group_id, Some(2));
+    assert!(res.is_ok());
+    let remaining: u32 = res.unwrap_json();
+    assert_eq!(remaining, 1);
+    let res = e.get_draft_group(draft_group_id).unwrap();
+    assert!(res.discarded);
+    assert_eq!(res.total_amount, amount);
+    assert_eq!(res.funded_amount, 0);
+    assert_eq!(e.get_draft_refunds(&e.owner), amount);
+
+    let res = e.cleanup_expired_draft_group(&users.dude, draft_group_id, Some(2));
+    let remaining: u32 = res.unwrap_json();
+    assert_eq!(remaining, 0);
+    assert!(e.get_draft_group(draft_group_id).is_none());
+    for draft_id in 0..3 {
+        assert!(e.get_draft(draft_id).is_none());
+    }
+    assert!(e.get_expired_draft_groups(None, None).is_empty());
+}
+
+#[test]
+fn test_draft_group_auto_convert() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(1000, TOKEN_DECIMALS);
+    let num_drafts = 25;
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let drafts: Vec<Draft> = (0..num_drafts)
+        .map(|_| Draft {
+            draft_group_id,
+            lockup_create: LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+            metadata: None,
+            created_at: 0,
+        })
+        .collect();
+    // the drafts don't fit into a single call with the default gas
+    for drafts in drafts.chunks(10) {
+        let res = e.create_drafts(&e.owner, &drafts.to_vec());
+        assert!(res.is_ok());
+    }
+
+    let res = e.set_draft_group_auto_convert(&users.alice, draft_group_id, true);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not the draft group creator"));
+
+    // only drafts that anyone can convert are converted automatically
+    let res = e.set_draft_group_conversion_policy(
+        &e.owner,
+        draft_group_id,
+        DraftConversionPolicy::Recipient,
+    );
+    assert!(res.is_ok());
+    let res = e.set_draft_group_auto_convert(&e.owner, draft_group_id, true);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("requires the Anyone conversion policy"));
+    let res = e.set_draft_group_conversion_policy(
+        &e.owner,
+        draft_group_id,
+        DraftConversionPolicy::Anyone,
+    );
+    assert!(res.is_ok());
+
+    let res = e.set_draft_group_auto_convert(&e.owner, draft_group_id, true);
+    assert!(res.is_ok());
+    assert!(e.get_draft_group(draft_group_id).unwrap().auto_convert);
+    let res = e.set_draft_group_conversion_policy(
+        &e.owner,
+        draft_group_id,
+        DraftConversionPolicy::Operators,
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("requires the Anyone conversion policy"));
+
+    // the funding is refunded if there is not enough gas for the next batch
+    let res =
+        e.fund_draft_group_with_gas(&e.owner, amount * num_drafts, draft_group_id, 120 * T_GAS);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, 0);
+    let draft_group = e.get_draft_group(draft_group_id).unwrap();
+    assert!(!draft_group.funded);
+    assert_eq!(draft_group.draft_indices.len(), num_drafts as usize);
+    assert!(e.get_account_lockups(&users.alice).is_empty());
+
+    // the first batch is converted on funding, the rest is converted by the self call
+    let res = e.fund_draft_group(&e.owner, amount * num_drafts, draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount * num_drafts);
+
+    assert!(e.get_draft_group(draft_group_id).is_none());
+    for draft_id in 0..num_drafts as DraftIndex {
+        assert!(e.get_draft(draft_id).is_none());
+    }
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), num_drafts as usize);
+    for (_index, lockup) in &lockups {
+        assert_eq!(lockup.total_balance, amount);
+        assert_eq!(lockup.account_id, users.alice.valid_account_id());
+    }
+}
+
+#[test]
+fn test_view_drafts_by_group_and_account() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let amount = d(60000, TOKEN_DECIMALS);
+    let build_draft = |draft_group_id, user: &UserAccount| Draft {
+        draft_group_id,
+        lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+        metadata: None,
+        created_at: 0,
+    };
+    let draft_ids = |drafts: Vec<(DraftIndex, DraftView)>| -> Vec<DraftIndex> {
+        drafts.into_iter().map(|(index, _)| index).collect()
+    };
+
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let other_draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let res = e.create_drafts(
+        &e.owner,
+        &vec![
+            build_draft(draft_group_id, &users.alice),
+            build_draft(draft_group_id, &users.bob),
+            build_draft(draft_group_id, &users.alice),
+            build_draft(other_draft_group_id, &users.alice),
+        ],
+    );
+    assert!(res.is_ok());
+
+    let res = e.get_drafts_by_group(draft_group_id, None, None);
+    assert_eq!(draft_ids(res), vec![0, 1, 2]);
+    let res = e.get_drafts_by_group(draft_group_id, Some(1), Some(1));
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0, 1);
+    assert_eq!(
+        res[0].1.lockup_create.account_id,
+        users.bob.valid_account_id()
+    );
+
+    assert_eq!(
+        draft_ids(e.get_drafts_by_account(&users.alice)),
+        vec![0, 2, 3]
+    );
+    assert_eq!(draft_ids(e.get_drafts_by_account(&users.bob)), vec![1]);
+    assert!(e.get_drafts_by_account(&users.charlie).is_empty());
+
+    // changing the receiver updates the index
+    let res = e.update_draft(
+        &e.owner,
+        2,
+        &LockupCreate::new_unlocked(users.charlie.valid_account_id(), amount),
+    );
+    assert!(res.is_ok());
+    assert_eq!(draft_ids(e.get_drafts_by_account(&users.alice)), vec![0, 3]);
+    assert_eq!(draft_ids(e.get_drafts_by_account(&users.charlie)), vec![2]);
+
+    // deleted drafts are removed from the index
+    let res = e.discard_draft_group(&e.owner, other_draft_group_id);
+    assert!(res.is_ok());
+    let res = e.delete_drafts(&e.owner, vec![3]);
+    assert!(res.is_ok());
+    assert_eq!(draft_ids(e.get_drafts_by_account(&users.alice)), vec![0]);
+
+    // converted drafts are removed from the index
+    let res = e.fund_draft_group(&e.owner, amount * 3, draft_group_id);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount * 3);
+    let res = e.convert_drafts(&users.dude, &vec![0, 1]);
+    assert!(res.is_ok());
+    assert!(e.get_drafts_by_account(&users.alice).is_empty());
+    assert!(e.get_drafts_by_account(&users.bob).is_empty());
+    assert_eq!(
+        draft_ids(e.get_drafts_by_group(draft_group_id, None, None)),
+        vec![2]
+    );
+}
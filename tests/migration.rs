@@ -0,0 +1,130 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_migrate_from_initial_release() {
+    let e = Env::init_v1(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    // the state of the initial release
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule)),
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    for user in vec![&users.bob, &users.alice] {
+        let lockup_create = LockupCreate::new_unlocked(user.valid_account_id(), amount);
+        let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    for user in vec![&users.charlie, &users.dude] {
+        let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+        let draft = Draft {
+            draft_group_id,
+            lockup_create: LockupCreate::new_unlocked(user.valid_account_id(), amount),
+            metadata: None,
+            created_at: 0,
+        };
+        let draft_id: DraftIndex = e.create_draft(&e.owner, &draft).unwrap_json();
+        assert_eq!(draft_id, draft_group_id);
+    }
+    let balance: WrappedBalance = e.fund_draft_group(&e.owner, amount, 0).unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    // upgrade
+    let res = e.upgrade(&users.eve);
+    assert!(res.is_ok());
+
+    assert_eq!(e.get_num_lockups(), 3);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].0, 0);
+    assert_eq!(lockups[0].1.creator_id, e.owner.valid_account_id());
+    let lockups = e.get_account_lockup_history(&users.alice, None, None);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].0, 2);
+    assert_eq!(lockups[0].1.claimed_balance, amount);
+    let lockups = e.get_account_lockups(&users.bob);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.creator_id, users.eve.valid_account_id());
+
+    let lockup_ids = |lockups: Vec<(LockupIndex, _)>| -> Vec<LockupIndex> {
+        lockups.into_iter().map(|(index, _)| index).collect()
+    };
+    assert_eq!(
+        lockup_ids(e.get_lockups_by_creator(&e.owner, None, None)),
+        vec![0]
+    );
+    assert_eq!(
+        lockup_ids(e.get_lockups_by_creator(&users.eve, None, None)),
+        vec![1, 2]
+    );
+    assert_eq!(
+        lockup_ids(e.get_terminable_lockups(&e.owner, None, None)),
+        vec![0]
+    );
+
+    let stats = e.get_stats();
+    assert_eq!(stats.total_deposited, amount * 4);
+    assert_eq!(stats.total_claimed, amount);
+    assert_eq!(stats.total_terminated, 0);
+    assert_eq!(stats.active_lockups, 2);
+    assert_eq!(stats.active_accounts, 2);
+
+    let draft_group = e.get_draft_group(0).unwrap();
+    assert!(draft_group.funded);
+    assert_eq!(draft_group.creator_id, users.eve.valid_account_id());
+    assert_eq!(draft_group.payer_id, Some(e.owner.valid_account_id()));
+    assert_eq!(draft_group.funded_amount, amount);
+    assert_eq!(draft_group.draft_indices, vec![0]);
+    let draft_group = e.get_draft_group(1).unwrap();
+    assert!(!draft_group.funded);
+    assert_eq!(draft_group.total_amount, amount);
+    assert_eq!(draft_group.draft_indices, vec![1]);
+    let draft = e.get_draft(1).unwrap();
+    assert_eq!(draft.draft_group_id, 1);
+    assert_eq!(
+        draft.lockup_create.account_id,
+        users.dude.valid_account_id()
+    );
+
+    let res = e.check_invariants(None, None);
+    assert!(res.is_last_page);
+    assert_eq!(res.lockups_balance, amount * 2);
+    assert_eq!(res.draft_groups_balance, amount);
+    assert_eq!(res.total_value_locked, amount * 3);
+    let res: bool = e.check_token_balance(&users.alice).unwrap_json();
+    assert!(res);
+
+    // the migrated state keeps working
+    let res: LockupIndex = e.convert_draft(&users.charlie, 0).unwrap_json();
+    assert_eq!(res, 3);
+    let lockups = e.get_account_lockups(&users.charlie);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount);
+
+    ft_storage_deposit(&users.bob, TOKEN_ID, &users.bob.account_id);
+    let res: WrappedBalance = e.claim(&users.bob).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert!(e.get_account_lockups(&users.bob).is_empty());
+    assert_eq!(
+        e.get_account_lockup_history(&users.bob, None, None).len(),
+        1
+    );
+
+    let stats = e.get_stats();
+    assert_eq!(stats.total_claimed, amount * 2);
+    assert_eq!(stats.active_lockups, 2);
+    assert_eq!(stats.active_accounts, 2);
+}
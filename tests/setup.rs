@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
+use near_sdk::borsh::BorshSerialize;
 pub use near_sdk::json_types::{Base58CryptoHash, ValidAccountId, WrappedBalance};
 use near_sdk::serde_json::json;
 use near_sdk::{env, serde_json, AccountId, Balance, Gas, Timestamp};
@@ -8,17 +9,34 @@ use near_sdk_sim::runtime::GenesisConfig;
 pub use near_sdk_sim::{
     deploy, init_simulator, to_yocto, ContractAccount, ExecutionResult, UserAccount, ViewResult,
 };
+use sha2::{Digest, Sha256};
 
-pub use ft_lockup::draft::{Draft, DraftGroupIndex, DraftIndex};
-use ft_lockup::ft_token_receiver::DraftGroupFunding;
-pub use ft_lockup::lockup::{Lockup, LockupCreate, LockupIndex};
-pub use ft_lockup::schedule::{Checkpoint, Schedule};
+pub use ft_lockup::airdrop::{AirdropCreate, AirdropIndex, MERKLE_LEAF_PREFIX, MERKLE_NODE_PREFIX};
+pub use ft_lockup::draft::{
+    Draft, DraftAcceptance, DraftConversionPolicy, DraftGroupFilter, DraftGroupIndex, DraftIndex,
+    DraftMetadata,
+};
+use ft_lockup::ft_token_receiver::{AirdropFunding, DraftGroupFunding};
+pub use ft_lockup::history::{LockupEvent, LockupEventKind, DEFAULT_LOCKUP_HISTORY_LIMIT};
+pub use ft_lockup::lockup::{
+    FractionalLockupCreate, Lockup, LockupCreate, LockupFilter, LockupIndex, LockupSort,
+    LockupSortKey,
+};
+pub use ft_lockup::schedule::{
+    Checkpoint, FractionalCheckpoint, FractionalSchedule, Schedule, BASIS_POINTS,
+};
+pub use ft_lockup::template::{ScheduleTemplate, TemplateId, TemplateLockupCreate};
 pub use ft_lockup::termination::{TerminationConfig, VestingConditions};
-use ft_lockup::view::{DraftGroupView, DraftView, LockupView};
+use ft_lockup::view::{
+    AccountSummaryView, AirdropView, InvariantsView, LockupBalanceView, LockupSearchView,
+    LockupView, StatsView,
+};
+pub use ft_lockup::view::{DraftGroupView, DraftView, InvariantsIndex};
 pub use ft_lockup::{ContractContract as FtLockupContract, TimestampSec};
 
 near_sdk_sim::lazy_static_include::lazy_static_include_bytes! {
     FT_LOCKUP_WASM_BYTES => "res/ft_lockup.wasm",
+    FT_LOCKUP_V1_WASM_BYTES => "res/ft_lockup_v1.wasm",
     FUNGIBLE_TOKEN_WASM_BYTES => "res/fungible_token.wasm",
 }
 
@@ -121,6 +139,38 @@ pub fn lockup_vesting_schedule_2(amount: u128) -> (Schedule, Schedule) {
     (lockup_schedule, vesting_schedule)
 }
 
+pub fn fractional_lockup_vesting_schedule() -> (FractionalSchedule, FractionalSchedule) {
+    let lockup_schedule = FractionalSchedule(vec![
+        FractionalCheckpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
+            basis_points: 0,
+        },
+        FractionalCheckpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
+            basis_points: BASIS_POINTS * 3 / 4,
+        },
+        FractionalCheckpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1,
+            basis_points: BASIS_POINTS,
+        },
+    ]);
+    let vesting_schedule = FractionalSchedule(vec![
+        FractionalCheckpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC - 1,
+            basis_points: 0,
+        },
+        FractionalCheckpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            basis_points: BASIS_POINTS / 4,
+        },
+        FractionalCheckpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4,
+            basis_points: BASIS_POINTS,
+        },
+    ]);
+    (lockup_schedule, vesting_schedule)
+}
+
 pub fn storage_deposit(
     user: &UserAccount,
     contract_id: &str,
@@ -163,6 +213,18 @@ pub fn to_nano(timestamp: u32) -> Timestamp {
 
 impl Env {
     pub fn init(deposit_whitelist: Option<Vec<ValidAccountId>>) -> Self {
+        Self::init_with_contract(&FT_LOCKUP_WASM_BYTES, deposit_whitelist)
+    }
+
+    /// Deploys the initial release of the contract to test the state migration.
+    pub fn init_v1(deposit_whitelist: Option<Vec<ValidAccountId>>) -> Self {
+        Self::init_with_contract(&FT_LOCKUP_V1_WASM_BYTES, deposit_whitelist)
+    }
+
+    fn init_with_contract(
+        ft_lockup_wasm_bytes: &[u8],
+        deposit_whitelist: Option<Vec<ValidAccountId>>,
+    ) -> Self {
         let mut genesis_config = GenesisConfig::default();
         genesis_config.block_prod_time = 0;
         let root = init_simulator(Some(genesis_config));
@@ -195,7 +257,7 @@ impl Env {
         let contract = deploy!(
             contract: FtLockupContract,
             contract_id: FT_LOCKUP_ID.to_string(),
-            bytes: &FT_LOCKUP_WASM_BYTES,
+            bytes: ft_lockup_wasm_bytes,
             signer_account: near,
             deposit: to_yocto("10"),
             gas: DEFAULT_GAS,
@@ -216,6 +278,22 @@ impl Env {
         }
     }
 
+    pub fn upgrade(&self, legacy_creator: &UserAccount) -> ExecutionResult {
+        self.contract
+            .user_account
+            .create_transaction(FT_LOCKUP_ID.to_string())
+            .deploy_contract(FT_LOCKUP_WASM_BYTES.to_vec())
+            .function_call(
+                "migrate".to_string(),
+                json!({ "legacy_creator_id": legacy_creator.valid_account_id() })
+                    .to_string()
+                    .into_bytes(),
+                MAX_GAS,
+                0,
+            )
+            .submit()
+    }
+
     pub fn ft_transfer(
         &self,
         sender: &UserAccount,
@@ -241,6 +319,16 @@ impl Env {
         user: &UserAccount,
         amount: Balance,
         msg: &str,
+    ) -> ExecutionResult {
+        self.ft_transfer_call_with_gas(user, amount, msg, MAX_GAS)
+    }
+
+    pub fn ft_transfer_call_with_gas(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        msg: &str,
+        gas: Gas,
     ) -> ExecutionResult {
         user.call(
             self.token.account_id.clone(),
@@ -252,7 +340,7 @@ impl Env {
             })
             .to_string()
             .into_bytes(),
-            MAX_GAS,
+            gas,
             1,
         )
     }
@@ -266,13 +354,80 @@ impl Env {
         self.ft_transfer_call(user, amount, &serde_json::to_string(lockup_create).unwrap())
     }
 
+    pub fn add_fractional_lockup(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        fractional_lockup_create: &FractionalLockupCreate,
+    ) -> ExecutionResult {
+        self.ft_transfer_call(
+            user,
+            amount,
+            &serde_json::to_string(fractional_lockup_create).unwrap(),
+        )
+    }
+
+    pub fn add_template_lockup(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        template_lockup_create: &TemplateLockupCreate,
+    ) -> ExecutionResult {
+        self.ft_transfer_call(
+            user,
+            amount,
+            &serde_json::to_string(template_lockup_create).unwrap(),
+        )
+    }
+
     pub fn fund_draft_group(
         &self,
         user: &UserAccount,
         amount: Balance,
         draft_group_id: DraftGroupIndex,
     ) -> ExecutionResult {
-        let funding = DraftGroupFunding { draft_group_id };
+        let funding = DraftGroupFunding {
+            draft_group_id,
+            cancellable_after: None,
+        };
+        self.ft_transfer_call(user, amount, &serde_json::to_string(&funding).unwrap())
+    }
+
+    pub fn fund_draft_group_with_gas(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        draft_group_id: DraftGroupIndex,
+        gas: Gas,
+    ) -> ExecutionResult {
+        let funding = DraftGroupFunding {
+            draft_group_id,
+            cancellable_after: None,
+        };
+        self.ft_transfer_call_with_gas(user, amount, &serde_json::to_string(&funding).unwrap(), gas)
+    }
+
+    pub fn fund_cancellable_draft_group(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        draft_group_id: DraftGroupIndex,
+        cancellable_after: TimestampSec,
+    ) -> ExecutionResult {
+        let funding = DraftGroupFunding {
+            draft_group_id,
+            cancellable_after: Some(cancellable_after),
+        };
+        self.ft_transfer_call(user, amount, &serde_json::to_string(&funding).unwrap())
+    }
+
+    pub fn fund_airdrop(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        airdrop_id: AirdropIndex,
+    ) -> ExecutionResult {
+        let funding = AirdropFunding { airdrop_id };
         self.ft_transfer_call(user, amount, &serde_json::to_string(&funding).unwrap())
     }
 
@@ -330,6 +485,31 @@ impl Env {
         )
     }
 
+    pub fn terminate_many(
+        &self,
+        user: &UserAccount,
+        lockups: &Vec<(LockupIndex, Option<Schedule>)>,
+        termination_timestamp: Option<TimestampSec>,
+    ) -> ExecutionResult {
+        self.terminate_many_with_gas(user, lockups, termination_timestamp, TERMINATE_GAS)
+    }
+
+    pub fn terminate_many_with_gas(
+        &self,
+        user: &UserAccount,
+        lockups: &Vec<(LockupIndex, Option<Schedule>)>,
+        termination_timestamp: Option<TimestampSec>,
+        gas: Gas,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .terminate_many(lockups.clone(), termination_timestamp),
+            gas,
+            1,
+        )
+    }
+
     pub fn remove_from_deposit_whitelist(
         &self,
         user: &UserAccount,
@@ -359,7 +539,164 @@ impl Env {
     }
 
     pub fn create_draft_group(&self, user: &UserAccount) -> ExecutionResult {
-        user.function_call(self.contract.contract.create_draft_group(), DEFAULT_GAS, 0)
+        self.create_draft_group_with_metadata(user, None)
+    }
+
+    pub fn create_draft_group_with_metadata(
+        &self,
+        user: &UserAccount,
+        metadata: Option<DraftMetadata>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.create_draft_group(metadata),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
+    pub fn add_draft_group_operator(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        account_id: &ValidAccountId,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .add_draft_group_operator(draft_group_id, account_id.clone()),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn remove_draft_group_operator(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        account_id: &ValidAccountId,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .remove_draft_group_operator(draft_group_id, account_id.clone()),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn set_draft_group_conversion_policy(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        conversion_policy: DraftConversionPolicy,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .set_draft_group_conversion_policy(draft_group_id, conversion_policy),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn set_draft_group_acceptance(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        acceptance: Option<DraftAcceptance>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .set_draft_group_acceptance(draft_group_id, acceptance),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn set_draft_group_auto_convert(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        auto_convert: bool,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .set_draft_group_auto_convert(draft_group_id, auto_convert),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn set_draft_group_beneficiary(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        beneficiary_id: Option<ValidAccountId>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .set_draft_group_beneficiary(draft_group_id, beneficiary_id),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn set_draft_group_funding_deadline(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        funding_deadline: Option<TimestampSec>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .set_draft_group_funding_deadline(draft_group_id, funding_deadline),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn cleanup_expired_draft_group(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        limit: Option<u32>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .cleanup_expired_draft_group(draft_group_id, limit),
+            MAX_GAS,
+            0,
+        )
+    }
+
+    pub fn accept_draft(
+        &self,
+        user: &UserAccount,
+        draft_id: DraftIndex,
+        terms_hash: Option<Base58CryptoHash>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.accept_draft(draft_id, terms_hash),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
+    pub fn reclaim_drafts(
+        &self,
+        user: &UserAccount,
+        draft_ids: Vec<DraftIndex>,
+    ) -> ExecutionResult {
+        user.function_call(self.contract.contract.reclaim_drafts(draft_ids), MAX_GAS, 0)
+    }
+
+    pub fn withdraw_draft_refunds(&self, user: &UserAccount) -> ExecutionResult {
+        user.function_call(self.contract.contract.withdraw_draft_refunds(), MAX_GAS, 0)
     }
 
     pub fn create_draft(&self, user: &UserAccount, draft: &Draft) -> ExecutionResult {
@@ -378,6 +715,36 @@ impl Env {
         )
     }
 
+    pub fn create_drafts_from_template(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+        lockups: &Vec<TemplateLockupCreate>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .create_drafts_from_template(draft_group_id, lockups.clone()),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
+    pub fn create_schedule_template(
+        &self,
+        user: &UserAccount,
+        template_id: &str,
+        template: &ScheduleTemplate,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .create_schedule_template(template_id.to_string(), template.clone()),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
     pub fn convert_draft(&self, user: &UserAccount, draft_id: DraftIndex) -> ExecutionResult {
         user.function_call(
             self.contract.contract.convert_draft(draft_id),
@@ -410,6 +777,18 @@ impl Env {
         )
     }
 
+    pub fn cancel_draft_group(
+        &self,
+        user: &UserAccount,
+        draft_group_id: DraftGroupIndex,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.cancel_draft_group(draft_group_id),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
     pub fn delete_drafts(&self, user: &UserAccount, draft_ids: Vec<DraftIndex>) -> ExecutionResult {
         user.function_call(
             self.contract.contract.delete_drafts(draft_ids),
@@ -418,6 +797,76 @@ impl Env {
         )
     }
 
+    pub fn update_draft(
+        &self,
+        user: &UserAccount,
+        draft_id: DraftIndex,
+        lockup_create: &LockupCreate,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .update_draft(draft_id, lockup_create.clone()),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
+    pub fn remove_draft(&self, user: &UserAccount, draft_id: DraftIndex) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.remove_draft(draft_id),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
+    pub fn move_drafts(
+        &self,
+        user: &UserAccount,
+        draft_ids: Vec<DraftIndex>,
+        draft_group_id: DraftGroupIndex,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .move_drafts(draft_ids, draft_group_id),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
+    pub fn create_airdrop(&self, user: &UserAccount, airdrop: &AirdropCreate) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.create_airdrop(airdrop.clone()),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
+    pub fn reclaim_airdrop(&self, user: &UserAccount, airdrop_id: AirdropIndex) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.reclaim_airdrop(airdrop_id),
+            CLAIM_GAS,
+            1,
+        )
+    }
+
+    pub fn claim_airdrop(
+        &self,
+        user: &UserAccount,
+        airdrop_id: AirdropIndex,
+        amount: Balance,
+        proof: &Vec<Base58CryptoHash>,
+    ) -> ExecutionResult {
+        user.function_call(
+            self.contract
+                .contract
+                .claim_airdrop(airdrop_id, amount.into(), proof.clone()),
+            DEFAULT_GAS,
+            0,
+        )
+    }
+
     pub fn get_num_lockups(&self) -> u32 {
         self.near
             .view_method_call(self.contract.contract.get_num_lockups())
@@ -440,6 +889,100 @@ impl Env {
             .unwrap_json()
     }
 
+    pub fn find_lockups(
+        &self,
+        filter: &LockupFilter,
+        from_index: Option<LockupIndex>,
+        limit: Option<u32>,
+    ) -> LockupSearchView {
+        self.find_lockups_sorted(filter, None, from_index, limit)
+    }
+
+    pub fn find_lockups_sorted(
+        &self,
+        filter: &LockupFilter,
+        sort: Option<LockupSort>,
+        from_index: Option<LockupIndex>,
+        limit: Option<u32>,
+    ) -> LockupSearchView {
+        self.near
+            .view_method_call(self.contract.contract.find_lockups(
+                filter.clone(),
+                sort,
+                from_index,
+                limit,
+            ))
+            .unwrap_json()
+    }
+
+    pub fn get_account_lockup_history(
+        &self,
+        user: &UserAccount,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.near
+            .view_method_call(self.contract.contract.get_account_lockup_history(
+                user.valid_account_id(),
+                from_index,
+                limit,
+            ))
+            .unwrap_json()
+    }
+
+    pub fn set_lockup_history_limit(&self, user: &UserAccount, limit: u32) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.set_lockup_history_limit(limit),
+            DEFAULT_GAS,
+            1,
+        )
+    }
+
+    pub fn get_lockup_history(
+        &self,
+        lockup_index: LockupIndex,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<LockupEvent> {
+        self.near
+            .view_method_call(self.contract.contract.get_lockup_history(
+                lockup_index,
+                from_index,
+                limit,
+            ))
+            .unwrap_json()
+    }
+
+    pub fn get_lockups_by_creator(
+        &self,
+        creator: &UserAccount,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.near
+            .view_method_call(self.contract.contract.get_lockups_by_creator(
+                creator.valid_account_id(),
+                from_index,
+                limit,
+            ))
+            .unwrap_json()
+    }
+
+    pub fn get_terminable_lockups(
+        &self,
+        beneficiary: &UserAccount,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(LockupIndex, LockupView)> {
+        self.near
+            .view_method_call(self.contract.contract.get_terminable_lockups(
+                beneficiary.valid_account_id(),
+                from_index,
+                limit,
+            ))
+            .unwrap_json()
+    }
+
     pub fn get_deposit_whitelist(&self) -> Vec<AccountId> {
         self.near
             .view_method_call(self.contract.contract.get_deposit_whitelist())
@@ -466,6 +1009,18 @@ impl Env {
             ))
     }
 
+    pub fn get_fractional_schedule(
+        &self,
+        schedule: &FractionalSchedule,
+        total_balance: Balance,
+    ) -> ViewResult {
+        self.near.view_method_call(
+            self.contract
+                .contract
+                .get_fractional_schedule(schedule.clone(), total_balance.into()),
+        )
+    }
+
     pub fn get_token_account_id(&self) -> ValidAccountId {
         self.near
             .view_method_call(self.contract.contract.get_token_account_id())
@@ -510,6 +1065,72 @@ impl Env {
             .unwrap_json()
     }
 
+    pub fn get_draft_groups_filtered(
+        &self,
+        filter: &DraftGroupFilter,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(DraftGroupIndex, DraftGroupView)> {
+        self.near
+            .view_method_call(self.contract.contract.get_draft_groups_filtered(
+                filter.clone(),
+                from_index,
+                limit,
+            ))
+            .unwrap_json()
+    }
+
+    pub fn get_expired_draft_groups(
+        &self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(DraftGroupIndex, DraftGroupView)> {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_expired_draft_groups(from_index, limit),
+            )
+            .unwrap_json()
+    }
+
+    pub fn get_drafts_by_group(
+        &self,
+        draft_group_id: DraftGroupIndex,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<(DraftIndex, DraftView)> {
+        self.near
+            .view_method_call(self.contract.contract.get_drafts_by_group(
+                draft_group_id,
+                from_index,
+                limit,
+            ))
+            .unwrap_json()
+    }
+
+    pub fn get_drafts_by_account(&self, user: &UserAccount) -> Vec<(DraftIndex, DraftView)> {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_drafts_by_account(user.valid_account_id()),
+            )
+            .unwrap_json()
+    }
+
+    pub fn get_draft_refunds(&self, user: &UserAccount) -> Balance {
+        let balance: WrappedBalance = self
+            .near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_draft_refunds(user.valid_account_id()),
+            )
+            .unwrap_json();
+        balance.0
+    }
+
     pub fn get_draft(&self, index: DraftIndex) -> Option<DraftView> {
         self.near
             .view_method_call(self.contract.contract.get_draft(index))
@@ -522,6 +1143,42 @@ impl Env {
             .unwrap_json()
     }
 
+    pub fn get_airdrop(&self, index: AirdropIndex) -> Option<AirdropView> {
+        self.near
+            .view_method_call(self.contract.contract.get_airdrop(index))
+            .unwrap_json()
+    }
+
+    pub fn is_airdrop_claimed(&self, index: AirdropIndex, user: &UserAccount) -> bool {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .is_airdrop_claimed(index, user.valid_account_id()),
+            )
+            .unwrap_json()
+    }
+
+    pub fn hash_airdrop_leaf(&self, user: &UserAccount, amount: Balance) -> Base58CryptoHash {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .hash_airdrop_leaf(user.valid_account_id(), amount.into()),
+            )
+            .unwrap_json()
+    }
+
+    pub fn get_schedule_template(&self, template_id: &str) -> Option<ScheduleTemplate> {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_schedule_template(template_id.to_string()),
+            )
+            .unwrap_json()
+    }
+
     pub fn get_account_lockups(&self, user: &UserAccount) -> Vec<(LockupIndex, LockupView)> {
         self.near
             .view_method_call(
@@ -532,6 +1189,72 @@ impl Env {
             .unwrap_json()
     }
 
+    pub fn get_lockup_balances(
+        &self,
+        lockup_index: LockupIndex,
+        timestamps: Option<Vec<TimestampSec>>,
+    ) -> Option<Vec<LockupBalanceView>> {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_lockup_balances(lockup_index, timestamps),
+            )
+            .unwrap_json()
+    }
+
+    pub fn get_account_balances(
+        &self,
+        user: &UserAccount,
+        timestamps: Option<Vec<TimestampSec>>,
+    ) -> Vec<LockupBalanceView> {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_account_balances(user.valid_account_id(), timestamps),
+            )
+            .unwrap_json()
+    }
+
+    pub fn get_stats(&self) -> StatsView {
+        self.near
+            .view_method_call(self.contract.contract.get_stats())
+            .unwrap_json()
+    }
+
+    pub fn check_invariants(
+        &self,
+        from_index: Option<InvariantsIndex>,
+        limit: Option<u32>,
+    ) -> InvariantsView {
+        self.near
+            .view_method_call(self.contract.contract.check_invariants(from_index, limit))
+            .unwrap_json()
+    }
+
+    pub fn check_token_balance(&self, user: &UserAccount) -> ExecutionResult {
+        user.function_call(
+            self.contract.contract.check_token_balance(),
+            DEFAULT_GAS * 3,
+            0,
+        )
+    }
+
+    pub fn get_account_summary(
+        &self,
+        user: &UserAccount,
+        timestamp: Option<TimestampSec>,
+    ) -> AccountSummaryView {
+        self.near
+            .view_method_call(
+                self.contract
+                    .contract
+                    .get_account_summary(user.valid_account_id(), timestamp),
+            )
+            .unwrap_json()
+    }
+
     pub fn get_lockup(&self, lockup_index: LockupIndex) -> LockupView {
         let lockup: Option<LockupView> = self
             .near
@@ -583,6 +1306,28 @@ impl Users {
     }
 }
 
+pub fn airdrop_leaf(user: &UserAccount, amount: Balance) -> [u8; 32] {
+    sha256(
+        &[
+            vec![MERKLE_LEAF_PREFIX],
+            (user.account_id.clone(), amount).try_to_vec().unwrap(),
+        ]
+        .concat(),
+    )
+}
+
+/// Combines two Merkle tree nodes the same way the contract does, in sorted order.
+pub fn merkle_node(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    sha256(&[&[MERKLE_NODE_PREFIX][..], &left, &right].concat())
+}
+
+fn sha256(value: &[u8]) -> [u8; 32] {
+    let mut res = [0u8; 32];
+    res.copy_from_slice(&Sha256::digest(value));
+    res
+}
+
 pub const fn d(value: Balance, decimals: u8) -> Balance {
     value * 10u128.pow(decimals as _)
 }
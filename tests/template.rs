@@ -0,0 +1,196 @@
+mod setup;
+
+use crate::setup::*;
+
+#[test]
+fn test_create_schedule_template() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(10000);
+    let template = ScheduleTemplate {
+        schedule: lockup_schedule.clone(),
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule.clone())),
+    };
+
+    // create by not authorized account
+    let res = e.create_schedule_template(&users.alice, "4y", &template);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    let res = e.create_schedule_template(&e.owner, "4y", &template);
+    assert!(res.is_ok());
+    assert_eq!(e.get_schedule_template("4y"), Some(template.clone()));
+    assert!(e.get_schedule_template("2y").is_none());
+
+    // cannot overwrite
+    let res = e.create_schedule_template(&e.owner, "4y", &template);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("schedule template already exists"));
+
+    // hashed vesting schedule cannot be scaled
+    let vesting_hash = e.hash_schedule(&vesting_schedule);
+    let template = ScheduleTemplate {
+        schedule: lockup_schedule.clone(),
+        vesting_schedule: Some(VestingConditions::Hash(vesting_hash)),
+    };
+    let res = e.create_schedule_template(&e.owner, "hashed", &template);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("cannot be used in a template"));
+
+    // vesting schedule behind the lockup schedule
+    let template = ScheduleTemplate {
+        schedule: vesting_schedule.clone(),
+        vesting_schedule: Some(VestingConditions::Schedule(lockup_schedule.clone())),
+    };
+    let res = e.create_schedule_template(&e.owner, "invalid", &template);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("The lockup schedule is ahead of"));
+}
+
+#[test]
+fn test_lockup_from_template() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(10000);
+    let template = ScheduleTemplate {
+        schedule: lockup_schedule.clone(),
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule.clone())),
+    };
+    assert!(e
+        .create_schedule_template(&e.owner, "4y", &template)
+        .is_ok());
+
+    // unknown template
+    let template_lockup_create = TemplateLockupCreate {
+        account_id: users.alice.valid_account_id(),
+        template_id: "2y".to_string(),
+        amount: amount.into(),
+    };
+    let res = e.add_template_lockup(&e.owner, amount, &template_lockup_create);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, 0);
+
+    let template_lockup_create = TemplateLockupCreate {
+        account_id: users.alice.valid_account_id(),
+        template_id: "4y".to_string(),
+        amount: amount.into(),
+    };
+
+    // amount mismatch
+    let res = e.add_template_lockup(&e.owner, amount / 2, &template_lockup_create);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, 0);
+
+    let res = e.add_template_lockup(&e.owner, amount, &template_lockup_create);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let (expected_lockup_schedule, expected_vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].1.total_balance, amount);
+    assert_eq!(lockups[0].1.schedule, expected_lockup_schedule);
+    assert_eq!(
+        lockups[0].1.termination_config,
+        Some(TerminationConfig {
+            beneficiary_id: e.owner.valid_account_id(),
+            vesting_schedule: VestingConditions::Schedule(expected_vesting_schedule),
+        })
+    );
+
+    // drafts from template
+    e.create_draft_group(&e.owner);
+    let lockups: Vec<TemplateLockupCreate> = vec![(&users.bob, amount), (&users.charlie, 12345)]
+        .into_iter()
+        .map(|(user, amount)| TemplateLockupCreate {
+            account_id: user.valid_account_id(),
+            template_id: "4y".to_string(),
+            amount: amount.into(),
+        })
+        .collect();
+    let res = e.create_drafts_from_template(&users.alice, 0, &lockups);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not in deposit whitelist"));
+
+    let res = e.create_drafts_from_template(&e.owner, 0, &lockups);
+    let draft_ids: Vec<DraftIndex> = res.unwrap_json();
+    assert_eq!(draft_ids, vec![0, 1]);
+    let draft_group = e.get_draft_group(0).unwrap();
+    assert_eq!(draft_group.total_amount, amount + 12345);
+
+    let draft = e.get_draft(1).unwrap();
+    assert_eq!(draft.lockup_create.total_balance, 12345);
+    assert_eq!(draft.lockup_create.schedule, lockup_schedule.scale(12345));
+}
+
+#[test]
+fn test_template_lockup_resolved_from_template() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(10000);
+    let template = ScheduleTemplate {
+        schedule: lockup_schedule.clone(),
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule.clone())),
+    };
+    assert!(e
+        .create_schedule_template(&e.owner, "4y", &template)
+        .is_ok());
+
+    let template_lockup_create = TemplateLockupCreate {
+        account_id: users.alice.valid_account_id(),
+        template_id: "4y".to_string(),
+        amount: amount.into(),
+    };
+    let res = e.add_template_lockup(&e.owner, amount, &template_lockup_create);
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let (expected_lockup_schedule, _) = lockup_vesting_schedule(amount);
+    let lockup = e.get_lockup(0);
+    assert_eq!(lockup.template_id, Some("4y".to_string()));
+    assert_eq!(lockup.schedule, expected_lockup_schedule);
+    assert_eq!(lockup.vested_balance, Some(0.into()));
+
+    // the converted draft keeps the template
+    e.create_draft_group(&e.owner);
+    let lockups = vec![TemplateLockupCreate {
+        account_id: users.bob.valid_account_id(),
+        template_id: "4y".to_string(),
+        amount: amount.into(),
+    }];
+    let res = e.create_drafts_from_template(&e.owner, 0, &lockups);
+    let draft_ids: Vec<DraftIndex> = res.unwrap_json();
+    assert_eq!(draft_ids, vec![0]);
+    assert!(e.fund_draft_group(&e.owner, amount, 0).is_ok());
+    let res = e.convert_draft(&users.bob, 0);
+    let lockup_index: LockupIndex = res.unwrap_json();
+    assert_eq!(lockup_index, 1);
+    let lockup = e.get_lockup(1);
+    assert_eq!(lockup.template_id, Some("4y".to_string()));
+    assert_eq!(lockup.schedule, expected_lockup_schedule);
+    assert!(lockup.terminable);
+
+    // vested at 1/4, the termination replaces the template schedule
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let lockup = e.get_lockup(0);
+    assert_eq!(lockup.vested_balance, Some((amount / 4).into()));
+    let res: WrappedBalance = e.terminate(&e.owner, 0).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    let lockup = e.get_lockup(0);
+    assert!(lockup.template_id.is_none());
+    assert!(lockup.termination_config.is_none());
+    assert_eq!(lockup.total_balance, amount / 4);
+
+    // other lockups of the template are not affected
+    let lockup = e.get_lockup(1);
+    assert_eq!(lockup.template_id, Some("4y".to_string()));
+    assert_eq!(lockup.total_balance, amount);
+}
@@ -96,14 +96,31 @@ fn test_terminate_basic_payer_logic() {
     let lockup_index = lockups[0].0;
 
     storage_force_unregister(&e.owner, TOKEN_ID);
-    // terminate with no storage deposit creates unlocked lockup
+    // terminate with no storage deposit restores the original lockup
     let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
     assert_eq!(res.0, 0);
     let lockups = e.get_account_lockups(&e.owner);
+    assert!(lockups.is_empty());
+    let lockups = e.get_account_lockups(&users.bob);
     assert_eq!(lockups.len(), 1);
-    assert_eq!(lockups[0].1.unclaimed_balance, amount);
+    assert_eq!(lockups[0].0, lockup_index);
+    assert_eq!(lockups[0].1.schedule, schedule);
+    assert_eq!(lockups[0].1.total_balance, amount);
+    assert_eq!(
+        lockups[0].1.termination_config,
+        Some(TerminationConfig {
+            beneficiary_id: e.owner.valid_account_id(),
+            vesting_schedule: VestingConditions::Schedule(schedule.clone()),
+        })
+    );
     let balance = e.ft_balance_of(&users.alice);
     assert_eq!(balance, 0);
+
+    // the termination can be retried
+    ft_storage_deposit(&e.owner, TOKEN_ID, &e.owner.account_id);
+    let res: WrappedBalance = e.terminate(&e.owner, lockup_index).unwrap_json();
+    assert_eq!(res.0, amount);
+    assert!(e.get_account_lockups(&users.bob).is_empty());
 }
 
 #[test]
@@ -789,3 +806,341 @@ fn test_lockup_terminate_custom_vesting_after_vesting_finished() {
     assert_eq!(lockup.claimed_balance, amount);
     assert_eq!(lockup.unclaimed_balance, 0);
 }
+
+#[test]
+fn test_terminate_many() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount * 3, &users.eve);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let vesting_hash = e.hash_schedule(&vesting_schedule);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule.clone(),
+        vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule.clone())),
+    };
+    let balance: WrappedBalance = e
+        .add_lockup(&users.eve, amount, &lockup_create)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let lockup_create = LockupCreate {
+        account_id: users.bob.valid_account_id(),
+        schedule: lockup_schedule.clone(),
+        vesting_schedule: Some(VestingConditions::Hash(vesting_hash)),
+    };
+    let balance: WrappedBalance = e
+        .add_lockup(&users.eve, amount, &lockup_create)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    let lockup_create = LockupCreate {
+        account_id: users.charlie.valid_account_id(),
+        schedule: lockup_schedule.clone(),
+        vesting_schedule: None,
+    };
+    let balance: WrappedBalance = e
+        .add_lockup(&users.eve, amount, &lockup_create)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    // 1Y, 1 / 4 vested, 0 unlocked
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+
+    // empty list
+    let res = e.terminate_many(&users.eve, &vec![], None);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("expected at least one lockup"));
+
+    // unauthorized
+    let res = e.terminate_many(&users.alice, &vec![(0, None)], None);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Unauthorized"));
+
+    // one of the lockups cannot be terminated, the whole batch fails
+    let res = e.terminate_many(
+        &users.eve,
+        &vec![(0, None), (1, Some(vesting_schedule.clone())), (2, None)],
+        None,
+    );
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("No termination config"));
+    assert_eq!(e.get_lockup(0).total_balance, amount);
+    assert_eq!(e.get_lockup(1).total_balance, amount);
+
+    // TERMINATE both in a single transfer
+    let res: WrappedBalance = e
+        .terminate_many(
+            &users.eve,
+            &vec![(0, None), (1, Some(vesting_schedule.clone()))],
+            None,
+        )
+        .unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4 * 2);
+    let terminator_balance = e.ft_balance_of(&users.eve);
+    assert_eq!(terminator_balance, amount * 3 / 4 * 2);
+
+    // Checking lockups
+    for user in vec![&users.alice, &users.bob] {
+        let lockups = e.get_account_lockups(user);
+        assert_eq!(lockups.len(), 1);
+        assert_eq!(lockups[0].1.total_balance, amount / 4);
+        assert_eq!(lockups[0].1.claimed_balance, 0);
+        assert!(lockups[0].1.termination_config.is_none());
+    }
+
+    // already terminated
+    let res = e.terminate_many(&users.eve, &vec![(0, None)], None);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("No termination config"));
+}
+
+#[test]
+fn test_get_terminable_lockups() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount * 3, &users.eve);
+
+    let schedule = Schedule(vec![
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC,
+            balance: 0,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            balance: amount,
+        },
+    ]);
+    let lockup_creates = vec![
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+        },
+        LockupCreate {
+            account_id: users.bob.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: None,
+        },
+        LockupCreate {
+            account_id: users.charlie.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: Some(VestingConditions::Hash(e.hash_schedule(&schedule))),
+        },
+    ];
+    for lockup_create in &lockup_creates {
+        let balance: WrappedBalance = e
+            .add_lockup(&users.eve, amount, lockup_create)
+            .unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+    let balance: WrappedBalance = e
+        .add_lockup(&e.owner, amount, &lockup_creates[0])
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC / 4);
+
+    // the lockup without termination config is not listed
+    let res = e.get_terminable_lockups(&users.eve, None, None);
+    assert_eq!(
+        res.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+        vec![0, 2]
+    );
+    assert_eq!(res[0].1.account_id, users.alice.valid_account_id());
+    assert_eq!(res[0].1.vested_balance, Some((amount / 4).into()));
+    // the hashed vesting schedule is unknown
+    assert_eq!(res[1].1.vested_balance, None);
+
+    let res = e.get_terminable_lockups(&users.eve, Some(1), Some(1));
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0, 2);
+
+    let res = e.get_terminable_lockups(&e.owner, None, None);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0, 3);
+
+    // the terminated lockup is no longer terminable
+    let res: WrappedBalance = e.terminate(&users.eve, 0).unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    let res = e.get_terminable_lockups(&users.eve, None, None);
+    assert_eq!(
+        res.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+        vec![2]
+    );
+
+    let res: WrappedBalance = e
+        .terminate_with_schedule(&users.eve, 2, schedule)
+        .unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4);
+    assert!(e.get_terminable_lockups(&users.eve, None, None).is_empty());
+}
+
+#[test]
+fn test_terminate_many_failed_transfer_restores_lockups() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount * 2, &users.eve);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let alice_termination_config = TerminationConfig {
+        beneficiary_id: users.eve.valid_account_id(),
+        vesting_schedule: VestingConditions::Schedule(vesting_schedule.clone()),
+    };
+    let bob_termination_config = TerminationConfig {
+        beneficiary_id: users.eve.valid_account_id(),
+        vesting_schedule: VestingConditions::SameAsLockupSchedule,
+    };
+    let lockup_creates = vec![
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: lockup_schedule.clone(),
+            vesting_schedule: Some(alice_termination_config.vesting_schedule.clone()),
+        },
+        LockupCreate {
+            account_id: users.bob.valid_account_id(),
+            schedule: lockup_schedule.clone(),
+            vesting_schedule: Some(bob_termination_config.vesting_schedule.clone()),
+        },
+    ];
+    for lockup_create in &lockup_creates {
+        let balance: WrappedBalance = e
+            .add_lockup(&users.eve, amount, lockup_create)
+            .unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+
+    // 1Y, 1 / 4 vested for alice, nothing vested for bob
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+
+    // the beneficiary is not registered with the token, the refund transfer fails
+    storage_force_unregister(&users.eve, TOKEN_ID);
+    let res: WrappedBalance = e
+        .terminate_many(&users.eve, &vec![(0, None), (1, None)], None)
+        .unwrap_json();
+    assert_eq!(res.0, 0);
+
+    // both lockups are restored, including the emptied one
+    for (lockup_index, user, termination_config) in vec![
+        (0, &users.alice, alice_termination_config),
+        (1, &users.bob, bob_termination_config),
+    ] {
+        let lockup = e.get_lockup(lockup_index);
+        assert_eq!(lockup.schedule, lockup_schedule);
+        assert_eq!(lockup.total_balance, amount);
+        assert_eq!(lockup.claimed_balance, 0);
+        assert_eq!(lockup.termination_config, Some(termination_config));
+
+        let lockups = e.get_account_lockups(user);
+        assert_eq!(lockups.len(), 1);
+        assert_eq!(lockups[0].0, lockup_index);
+        assert!(e.get_account_lockup_history(user, None, None).is_empty());
+    }
+    let res = e.get_terminable_lockups(&users.eve, None, None);
+    assert_eq!(
+        res.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    let stats = e.get_stats();
+    assert_eq!(stats.total_terminated, 0);
+    assert_eq!(stats.active_lockups, 2);
+    assert_eq!(stats.active_accounts, 2);
+
+    // the termination can be retried once the beneficiary is registered
+    ft_storage_deposit(&users.eve, TOKEN_ID, &users.eve.account_id);
+    let res: WrappedBalance = e
+        .terminate_many(&users.eve, &vec![(0, None), (1, None)], None)
+        .unwrap_json();
+    assert_eq!(res.0, amount * 3 / 4 + amount);
+    assert_eq!(e.ft_balance_of(&users.eve), amount * 3 / 4 + amount);
+    assert!(e.get_account_lockups(&users.bob).is_empty());
+    assert!(e.get_terminable_lockups(&users.eve, None, None).is_empty());
+}
+
+#[test]
+fn test_terminate_many_failed_transfer_restores_large_batch() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    let num_lockups: LockupIndex = 5;
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount * num_lockups as u128, &users.eve);
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let termination_config = TerminationConfig {
+        beneficiary_id: users.eve.valid_account_id(),
+        vesting_schedule: VestingConditions::Schedule(vesting_schedule),
+    };
+    let accounts = vec![
+        &users.alice,
+        &users.bob,
+        &users.charlie,
+        &users.dude,
+        &users.alice,
+    ];
+    for account in &accounts {
+        let lockup_create = LockupCreate {
+            account_id: account.valid_account_id(),
+            schedule: lockup_schedule.clone(),
+            vesting_schedule: Some(termination_config.vesting_schedule.clone()),
+        };
+        let balance: WrappedBalance = e
+            .add_lockup(&users.eve, amount, &lockup_create)
+            .unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+    let lockups: Vec<(LockupIndex, Option<Schedule>)> =
+        (0..num_lockups).map(|index| (index, None)).collect();
+
+    // 1Y, 1 / 4 vested
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+
+    // the callback gas grows with the number of lockups
+    let res = e.terminate_many(&users.eve, &lockups, None);
+    assert!(!res.is_ok());
+    assert!(format!("{:?}", res.status()).contains("Not enough gas to terminate 5 lockups"));
+
+    // the beneficiary is not registered with the token, the refund transfer fails
+    storage_force_unregister(&users.eve, TOKEN_ID);
+    let res: WrappedBalance = e
+        .terminate_many_with_gas(&users.eve, &lockups, None, MAX_GAS)
+        .unwrap_json();
+    assert_eq!(res.0, 0);
+
+    // all lockups are restored
+    for lockup_index in 0..num_lockups {
+        let lockup = e.get_lockup(lockup_index);
+        assert_eq!(lockup.schedule, lockup_schedule);
+        assert_eq!(lockup.claimed_balance, 0);
+        assert_eq!(lockup.termination_config, Some(termination_config.clone()));
+    }
+    assert_eq!(e.get_account_lockups(&users.alice).len(), 2);
+    let res = e.get_terminable_lockups(&users.eve, None, None);
+    assert_eq!(res.len(), num_lockups as usize);
+    let stats = e.get_stats();
+    assert_eq!(stats.total_terminated, 0);
+    assert_eq!(stats.active_lockups, num_lockups as u64);
+}
@@ -50,6 +50,38 @@ fn test_validate_schedule() {
     assert!(format!("{:?}", res.unwrap_err()).contains("The lockup schedule is ahead of"));
 }
 
+#[test]
+fn test_get_fractional_schedule() {
+    let e = Env::init(None);
+    let amount = d(60000, TOKEN_DECIMALS);
+    let (fractional_lockup_schedule, fractional_vesting_schedule) =
+        fractional_lockup_vesting_schedule();
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+
+    let res: Schedule = e
+        .get_fractional_schedule(&fractional_lockup_schedule, amount)
+        .unwrap_json();
+    assert_eq!(res, lockup_schedule);
+    let res: Schedule = e
+        .get_fractional_schedule(&fractional_vesting_schedule, amount)
+        .unwrap_json();
+    assert_eq!(res, vesting_schedule);
+
+    // rounding down, the last checkpoint is equal to the total balance
+    let res: Schedule = e
+        .get_fractional_schedule(&fractional_lockup_schedule, 3)
+        .unwrap_json();
+    let balances: Vec<u128> = res.0.iter().map(|checkpoint| checkpoint.balance).collect();
+    assert_eq!(balances, vec![0, 2, 3]);
+
+    // the last checkpoint is not 100%
+    let mut invalid_schedule = fractional_lockup_schedule.clone();
+    invalid_schedule.0.last_mut().unwrap().basis_points = BASIS_POINTS - 1;
+    let res = e.get_fractional_schedule(&invalid_schedule, amount);
+    assert!(format!("{:?}", res.unwrap_err())
+        .contains("The last checkpoint should unlock 10000 basis points"));
+}
+
 #[test]
 fn test_get_lockups() {
     let e = Env::init(None);
@@ -113,3 +145,744 @@ fn test_get_token_account_id() {
     let result = e.get_token_account_id();
     assert_eq!(result, e.token.valid_account_id());
 }
+
+#[test]
+fn test_get_account_summary() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let summary = e.get_account_summary(&users.alice, None);
+    assert_eq!(summary.num_lockups, 0);
+    assert_eq!(summary.total_balance, 0);
+    assert!(summary.next_unlock_timestamp.is_none());
+
+    let (lockup_schedule, vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_creates = vec![
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: lockup_schedule.clone(),
+            vesting_schedule: Some(VestingConditions::Schedule(vesting_schedule.clone())),
+        },
+        LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: lockup_schedule.clone(),
+            vesting_schedule: Some(VestingConditions::Hash(e.hash_schedule(&vesting_schedule))),
+        },
+    ];
+    for lockup_create in &lockup_creates {
+        let balance: WrappedBalance = e.add_lockup(&e.owner, amount, lockup_create).unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let summary = e.get_account_summary(&users.alice, None);
+    assert_eq!(summary.timestamp, GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    assert_eq!(summary.num_lockups, 3);
+    assert_eq!(summary.total_balance, amount * 3);
+    assert_eq!(summary.unlocked_balance, amount);
+    assert_eq!(summary.locked_balance, amount * 2);
+    assert_eq!(summary.claimed_balance, 0);
+    assert_eq!(summary.claimable_balance, amount);
+    assert_eq!(summary.vested_balance, amount / 4 + amount);
+    assert_eq!(summary.unvested_balance, amount * 3 / 4);
+    assert_eq!(summary.hidden_vesting_balance, amount);
+    assert_eq!(
+        summary.next_unlock_timestamp,
+        Some(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4)
+    );
+
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    // the fully claimed lockup is archived and no longer counted
+    let summary = e.get_account_summary(&users.alice, None);
+    assert_eq!(summary.num_lockups, 2);
+    assert_eq!(summary.total_balance, amount * 2);
+    assert_eq!(summary.claimed_balance, 0);
+    assert_eq!(summary.claimable_balance, 0);
+
+    // at the future timestamp
+    let summary = e.get_account_summary(
+        &users.alice,
+        Some(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1),
+    );
+    assert_eq!(summary.unlocked_balance, amount * 2);
+    assert_eq!(summary.locked_balance, 0);
+    assert_eq!(summary.claimable_balance, amount * 2);
+    assert_eq!(summary.vested_balance, amount);
+    assert_eq!(summary.unvested_balance, 0);
+    assert!(summary.next_unlock_timestamp.is_none());
+}
+
+#[test]
+fn test_get_balances_at_timestamps() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let (lockup_schedule, _vesting_schedule) = lockup_vesting_schedule(amount);
+    let lockup_create = LockupCreate {
+        account_id: users.alice.valid_account_id(),
+        schedule: lockup_schedule,
+        vesting_schedule: None,
+    };
+    let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+    assert_eq!(balance.0, amount);
+    let res = e.add_lockup(
+        &e.owner,
+        amount,
+        &LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+    );
+    let balance: WrappedBalance = res.unwrap_json();
+    assert_eq!(balance.0, amount);
+    let lockups = e.get_account_lockups(&users.alice);
+    let lockup_index = lockups
+        .iter()
+        .find(|(_index, lockup)| lockup.schedule.0.len() > 2)
+        .unwrap()
+        .0;
+
+    assert!(e.get_lockup_balances(lockup_index + 2, None).is_none());
+
+    // the current timestamp by default
+    let balances = e.get_lockup_balances(lockup_index, None).unwrap();
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].timestamp, GENESIS_TIMESTAMP_SEC);
+    assert_eq!(balances[0].unlocked_balance, 0);
+
+    let timestamps = vec![
+        GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
+        GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3,
+        GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 4 + 1,
+    ];
+    let balances = e
+        .get_lockup_balances(lockup_index, Some(timestamps.clone()))
+        .unwrap();
+    assert_eq!(
+        balances
+            .iter()
+            .map(|balance| (balance.timestamp, balance.unlocked_balance))
+            .collect::<Vec<_>>(),
+        vec![
+            (timestamps[0], 0),
+            (timestamps[1], amount * 3 / 8),
+            (timestamps[2], amount),
+        ]
+    );
+
+    // the fully claimed lockup is archived and no longer counted,
+    // the claimable balance accounts for the claimed balance
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3);
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount + amount * 3 / 8);
+    assert_eq!(e.get_account_lockups(&users.alice).len(), 1);
+
+    let balances = e.get_account_balances(&users.alice, Some(timestamps.clone()));
+    assert_eq!(
+        balances
+            .iter()
+            .map(|balance| (balance.unlocked_balance, balance.claimable_balance))
+            .collect::<Vec<_>>(),
+        vec![(0, 0), (amount * 3 / 8, 0), (amount, amount * 5 / 8)]
+    );
+}
+
+#[test]
+fn test_get_stats() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let stats = e.get_stats();
+    assert_eq!(stats.total_deposited, 0);
+    assert_eq!(stats.active_lockups, 0);
+    assert_eq!(stats.active_accounts, 0);
+
+    let schedule = Schedule(vec![
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC,
+            balance: 0,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            balance: amount,
+        },
+    ]);
+    let lockup_creates = vec![
+        LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+        },
+        LockupCreate {
+            account_id: users.bob.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+        },
+    ];
+    for lockup_create in &lockup_creates {
+        let balance: WrappedBalance = e.add_lockup(&e.owner, amount, lockup_create).unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+
+    let stats = e.get_stats();
+    assert_eq!(stats.total_deposited, amount * 3);
+    assert_eq!(stats.total_value_locked, amount * 3);
+    assert_eq!(stats.active_lockups, 3);
+    assert_eq!(stats.active_accounts, 2);
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC / 2);
+    let bob_lockup_index = e.get_account_lockups(&users.bob)[0].0;
+    let res: WrappedBalance = e.terminate(&e.owner, bob_lockup_index).unwrap_json();
+    assert_eq!(res.0, amount / 2);
+
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount + amount / 2);
+
+    let stats = e.get_stats();
+    assert_eq!(stats.total_deposited, amount * 3);
+    assert_eq!(stats.total_claimed, amount + amount / 2);
+    assert_eq!(stats.total_terminated, amount / 2);
+    assert_eq!(stats.total_value_locked, amount);
+    assert_eq!(stats.active_lockups, 2);
+    assert_eq!(stats.active_accounts, 2);
+
+    // fully claimed lockups are no longer active
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount / 2);
+
+    let stats = e.get_stats();
+    assert_eq!(stats.total_claimed, amount * 2);
+    assert_eq!(stats.total_value_locked, amount / 2);
+    assert_eq!(stats.active_lockups, 1);
+    assert_eq!(stats.active_accounts, 1);
+}
+
+#[test]
+fn test_check_invariants() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.check_invariants(None, None);
+    assert!(res.is_last_page);
+    assert_eq!(res.total_value_locked, 0);
+    let res: bool = e.check_token_balance(&users.alice).unwrap_json();
+    assert!(res);
+
+    for user in vec![&users.alice, &users.bob] {
+        let lockup_create = LockupCreate::new_unlocked(user.valid_account_id(), amount);
+        let balance: WrappedBalance = e.add_lockup(&e.owner, amount, &lockup_create).unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+
+    let draft_group_id: DraftGroupIndex = e.create_draft_group(&e.owner).unwrap_json();
+    let res = e.create_drafts(
+        &e.owner,
+        &vec![Draft {
+            draft_group_id,
+            lockup_create: LockupCreate::new_unlocked(users.charlie.valid_account_id(), amount),
+            metadata: None,
+            created_at: 0,
+        }],
+    );
+    assert!(res.is_ok());
+    let balance: WrappedBalance = e
+        .fund_draft_group(&e.owner, amount, draft_group_id)
+        .unwrap_json();
+    assert_eq!(balance.0, amount);
+
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    let res = e.check_invariants(None, None);
+    assert!(res.is_last_page);
+    assert_eq!(res.lockups_balance, amount);
+    assert_eq!(res.draft_groups_balance, amount);
+    assert_eq!(res.airdrops_balance, 0);
+    assert_eq!(res.pending_draft_refunds, 0);
+    assert_eq!(res.total_value_locked, amount * 2);
+
+    // each collection is paged separately
+    let first_page = e.check_invariants(None, Some(1));
+    assert!(!first_page.is_last_page);
+    assert_eq!(
+        first_page.to_index,
+        InvariantsIndex {
+            lockups: 1,
+            draft_groups: 1,
+            airdrops: 0,
+        }
+    );
+    let second_page = e.check_invariants(Some(first_page.to_index.clone()), Some(1));
+    assert!(second_page.is_last_page);
+    assert_eq!(
+        second_page.to_index,
+        InvariantsIndex {
+            lockups: 2,
+            draft_groups: 1,
+            airdrops: 0,
+        }
+    );
+    assert_eq!(
+        first_page.lockups_balance + second_page.lockups_balance,
+        amount
+    );
+    assert_eq!(
+        first_page.draft_groups_balance + second_page.draft_groups_balance,
+        amount
+    );
+
+    let res: bool = e.check_token_balance(&users.alice).unwrap_json();
+    assert!(res);
+    assert_eq!(e.ft_balance_of(&e.contract.user_account), amount * 2);
+
+    // a surplus is not a solvency violation
+    e.ft_transfer(&e.owner, amount, &e.contract.user_account);
+    let res: bool = e.check_token_balance(&users.alice).unwrap_json();
+    assert!(res);
+}
+
+#[test]
+fn test_get_lockups_by_creator() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount, &users.eve);
+
+    let lockup_creates = vec![
+        (&e.owner, users.alice.valid_account_id()),
+        (&users.eve, users.bob.valid_account_id()),
+        (&e.owner, users.bob.valid_account_id()),
+        (&e.owner, users.charlie.valid_account_id()),
+    ];
+    for (creator, account_id) in lockup_creates {
+        let lockup_create = LockupCreate::new_unlocked(account_id, amount);
+        let balance: WrappedBalance = e.add_lockup(creator, amount, &lockup_create).unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+
+    // unterminable lockups keep the creator
+    let lockup = e.get_lockup(1);
+    assert!(lockup.termination_config.is_none());
+    assert_eq!(lockup.creator_id, users.eve.valid_account_id());
+
+    let res = e.get_lockups_by_creator(&e.owner, None, None);
+    assert_eq!(
+        res.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+        vec![0, 2, 3]
+    );
+    assert!(res
+        .iter()
+        .all(|(_, lockup)| lockup.creator_id == e.owner.valid_account_id()));
+    assert_eq!(res[1].1.account_id, users.bob.valid_account_id());
+
+    let res = e.get_lockups_by_creator(&e.owner, Some(1), Some(1));
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0, 2);
+
+    let res = e.get_lockups_by_creator(&users.eve, None, None);
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].0, 1);
+
+    let res = e.get_lockups_by_creator(&users.alice, None, None);
+    assert!(res.is_empty());
+}
+
+#[test]
+fn test_find_lockups() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let res = e.add_to_deposit_whitelist(&e.owner, &users.eve.valid_account_id());
+    assert!(res.is_ok());
+    ft_storage_deposit(&e.owner, TOKEN_ID, &users.eve.account_id);
+    e.ft_transfer(&e.owner, amount, &users.eve);
+
+    let schedule = Schedule(vec![
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC,
+            balance: 0,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            balance: 0,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
+            balance: amount,
+        },
+    ]);
+    // 0: alice, unlocked, not terminable
+    // 1: bob, after a cliff, terminable, created by eve
+    // 2: alice, after a cliff, terminable
+    // 3: charlie, half amount, unlocked
+    let lockups = vec![
+        (
+            &e.owner,
+            amount,
+            LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        ),
+        (
+            &users.eve,
+            amount,
+            LockupCreate {
+                account_id: users.bob.valid_account_id(),
+                schedule: schedule.clone(),
+                vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+            },
+        ),
+        (
+            &e.owner,
+            amount,
+            LockupCreate {
+                account_id: users.alice.valid_account_id(),
+                schedule: schedule.clone(),
+                vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+            },
+        ),
+        (
+            &e.owner,
+            amount / 2,
+            LockupCreate::new_unlocked(users.charlie.valid_account_id(), amount / 2),
+        ),
+    ];
+    for (creator, amount, lockup_create) in &lockups {
+        let balance: WrappedBalance = e.add_lockup(creator, *amount, lockup_create).unwrap_json();
+        assert_eq!(balance.0, *amount);
+    }
+
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    let find = |filter: LockupFilter| -> Vec<LockupIndex> {
+        let res = e.find_lockups(&filter, None, None);
+        assert_eq!(res.next_index, None);
+        res.lockups.into_iter().map(|(index, _)| index).collect()
+    };
+
+    assert_eq!(find(LockupFilter::default()), vec![0, 1, 2, 3]);
+    assert_eq!(
+        find(LockupFilter {
+            account_id: Some(users.alice.valid_account_id()),
+            ..Default::default()
+        }),
+        vec![0, 2]
+    );
+    assert_eq!(
+        find(LockupFilter {
+            creator_id: Some(e.owner.valid_account_id()),
+            ..Default::default()
+        }),
+        vec![0, 2, 3]
+    );
+    assert_eq!(
+        find(LockupFilter {
+            terminable: Some(true),
+            ..Default::default()
+        }),
+        vec![1, 2]
+    );
+    assert_eq!(
+        find(LockupFilter {
+            fully_claimed: Some(true),
+            ..Default::default()
+        }),
+        vec![0]
+    );
+    assert_eq!(
+        find(LockupFilter {
+            unlock_start_from: Some(GENESIS_TIMESTAMP_SEC),
+            ..Default::default()
+        }),
+        vec![1, 2]
+    );
+    assert_eq!(
+        find(LockupFilter {
+            unlock_start_to: Some(GENESIS_TIMESTAMP_SEC),
+            ..Default::default()
+        }),
+        vec![0, 3]
+    );
+    assert_eq!(
+        find(LockupFilter {
+            min_total_balance: Some(amount.into()),
+            fully_claimed: Some(false),
+            ..Default::default()
+        }),
+        vec![1, 2]
+    );
+    assert_eq!(
+        find(LockupFilter {
+            creator_id: Some(e.owner.valid_account_id()),
+            terminable: Some(true),
+            ..Default::default()
+        }),
+        vec![2]
+    );
+
+    // cursor pagination
+    let filter = LockupFilter {
+        terminable: Some(false),
+        ..Default::default()
+    };
+    let res = e.find_lockups(&filter, None, Some(1));
+    assert_eq!(res.lockups.len(), 1);
+    assert_eq!(res.lockups[0].0, 0);
+    assert_eq!(res.lockups[0].1.account_id, users.alice.valid_account_id());
+    assert_eq!(res.next_index, Some(1));
+    let res = e.find_lockups(&filter, res.next_index, Some(1));
+    assert_eq!(res.lockups.len(), 1);
+    assert_eq!(res.lockups[0].0, 3);
+    assert_eq!(res.next_index, None);
+
+    let find_sorted = |filter: LockupFilter, key: LockupSortKey, descending: bool| {
+        let res = e.find_lockups_sorted(&filter, Some(LockupSort { key, descending }), None, None);
+        assert_eq!(res.next_index, None);
+        res.lockups
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect::<Vec<LockupIndex>>()
+    };
+    let alice_filter = LockupFilter {
+        account_id: Some(users.alice.valid_account_id()),
+        ..Default::default()
+    };
+    let owner_filter = LockupFilter {
+        creator_id: Some(e.owner.valid_account_id()),
+        ..Default::default()
+    };
+    assert_eq!(
+        find_sorted(LockupFilter::default(), LockupSortKey::Index, true),
+        vec![3, 2, 1, 0]
+    );
+    assert_eq!(
+        find_sorted(owner_filter.clone(), LockupSortKey::Index, true),
+        vec![3, 2, 0]
+    );
+    // the archived lockup of alice is sorted too
+    assert_eq!(
+        find_sorted(alice_filter.clone(), LockupSortKey::UnlockStart, false),
+        vec![0, 2]
+    );
+    assert_eq!(
+        find_sorted(alice_filter.clone(), LockupSortKey::UnlockStart, true),
+        vec![2, 0]
+    );
+    // equal balances are ordered by the lockup index
+    assert_eq!(
+        find_sorted(alice_filter.clone(), LockupSortKey::TotalBalance, true),
+        vec![2, 0]
+    );
+
+    // descending cursor pagination
+    let sort = Some(LockupSort {
+        key: LockupSortKey::Index,
+        descending: true,
+    });
+    let res = e.find_lockups_sorted(&filter, sort, None, Some(1));
+    assert_eq!(res.lockups[0].0, 3);
+    assert_eq!(res.next_index, Some(2));
+    let res = e.find_lockups_sorted(&filter, sort, res.next_index, Some(1));
+    assert_eq!(res.lockups[0].0, 0);
+    assert_eq!(res.next_index, None);
+
+    let res = e.find_lockups_sorted(&owner_filter, sort, None, Some(2));
+    assert_eq!(
+        res.lockups
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<_>>(),
+        vec![3, 2]
+    );
+    assert_eq!(res.next_index, Some(0));
+    let res = e.find_lockups_sorted(&owner_filter, sort, res.next_index, Some(2));
+    assert_eq!(res.lockups.len(), 1);
+    assert_eq!(res.lockups[0].0, 0);
+    assert_eq!(res.next_index, None);
+
+    // the cursor of the sorted account lockups
+    let sort = Some(LockupSort {
+        key: LockupSortKey::UnlockStart,
+        descending: true,
+    });
+    let res = e.find_lockups_sorted(&alice_filter, sort, None, Some(1));
+    assert_eq!(res.lockups[0].0, 2);
+    assert_eq!(res.next_index, Some(0));
+    let res = e.find_lockups_sorted(&alice_filter, sort, res.next_index, Some(1));
+    assert_eq!(res.lockups[0].0, 0);
+    assert_eq!(res.next_index, None);
+}
+
+#[test]
+fn test_get_account_lockup_history() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let schedule = Schedule(vec![
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            balance: 0,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
+            balance: amount,
+        },
+    ]);
+    let lockup_creates = vec![
+        LockupCreate::new_unlocked(users.alice.valid_account_id(), amount),
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+        },
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: None,
+        },
+    ];
+    for lockup_create in &lockup_creates {
+        let balance: WrappedBalance = e.add_lockup(&e.owner, amount, lockup_create).unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+    assert!(e
+        .get_account_lockup_history(&users.alice, None, None)
+        .is_empty());
+
+    // the fully claimed lockup is archived
+    ft_storage_deposit(&users.alice, TOKEN_ID, &users.alice.account_id);
+    let res: WrappedBalance = e.claim(&users.alice).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    // the lockup terminated before the cliff is archived
+    let res: WrappedBalance = e.terminate(&e.owner, 1).unwrap_json();
+    assert_eq!(res.0, amount);
+
+    let lockups = e.get_account_lockups(&users.alice);
+    assert_eq!(lockups.len(), 1);
+    assert_eq!(lockups[0].0, 2);
+
+    let history = e.get_account_lockup_history(&users.alice, None, None);
+    assert_eq!(
+        history.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    assert_eq!(history[0].1.claimed_balance, amount);
+    assert_eq!(history[0].1.total_balance, amount);
+    assert_eq!(history[1].1.claimed_balance, 0);
+    assert_eq!(history[1].1.total_balance, 0);
+    assert!(history[1].1.termination_config.is_none());
+
+    let history = e.get_account_lockup_history(&users.alice, Some(1), Some(1));
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0, 1);
+
+    assert!(e
+        .get_account_lockup_history(&users.bob, None, None)
+        .is_empty());
+}
+
+#[test]
+fn test_lockup_view_unlock_progress() {
+    let e = Env::init(None);
+    let users = Users::init(&e);
+    let amount = d(60000, TOKEN_DECIMALS);
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC);
+
+    let schedule = Schedule(vec![
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC,
+            balance: 0,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC,
+            balance: 0,
+        },
+        Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
+            balance: amount,
+        },
+    ]);
+    let lockup_creates = vec![
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: Some(VestingConditions::SameAsLockupSchedule),
+        },
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: Some(VestingConditions::Hash(e.hash_schedule(&schedule))),
+        },
+        LockupCreate {
+            account_id: users.alice.valid_account_id(),
+            schedule: schedule.clone(),
+            vesting_schedule: None,
+        },
+    ];
+    for lockup_create in &lockup_creates {
+        let balance: WrappedBalance = e.add_lockup(&e.owner, amount, lockup_create).unwrap_json();
+        assert_eq!(balance.0, amount);
+    }
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 3 / 2);
+    let lockup = e.get_lockup(0);
+    assert_eq!(lockup.unlocked_balance, amount / 2);
+    assert_eq!(lockup.locked_balance, amount / 2);
+    assert_eq!(lockup.unclaimed_balance, amount / 2);
+    assert_eq!(lockup.unlocked_basis_points, BASIS_POINTS / 2);
+    assert_eq!(
+        lockup.next_checkpoint,
+        Some(Checkpoint {
+            timestamp: GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2,
+            balance: amount,
+        })
+    );
+    assert_eq!(lockup.start_timestamp, GENESIS_TIMESTAMP_SEC);
+    assert_eq!(
+        lockup.end_timestamp,
+        GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2
+    );
+    assert!(lockup.terminable);
+    assert_eq!(lockup.vested_balance, Some((amount / 2).into()));
+
+    // the hashed vesting schedule is unknown
+    let lockup = e.get_lockup(1);
+    assert!(lockup.terminable);
+    assert_eq!(lockup.vested_balance, None);
+
+    // lockups without termination config are fully vested
+    let lockup = e.get_lockup(2);
+    assert!(!lockup.terminable);
+    assert_eq!(lockup.vested_balance, Some(amount.into()));
+
+    e.set_time_sec(GENESIS_TIMESTAMP_SEC + ONE_YEAR_SEC * 2);
+    let lockup = e.get_lockup(0);
+    assert_eq!(lockup.unlocked_balance, amount);
+    assert_eq!(lockup.locked_balance, 0);
+    assert_eq!(lockup.unlocked_basis_points, BASIS_POINTS);
+    assert_eq!(lockup.next_checkpoint, None);
+}